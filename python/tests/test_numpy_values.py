@@ -0,0 +1,43 @@
+"""Runtime tests for numpy-array parameter values.
+
+Run after `maturin develop`:
+    pytest python/tests/test_numpy_values.py -q
+"""
+
+import numpy as np
+import pytest
+from minuit2 import Minuit
+
+
+def quad(x, y):
+    # Minimum at (1, 2), fval = 0.
+    return (x - 1.0) ** 2 + (y - 2.0) ** 2
+
+
+def test_init_accepts_values_array():
+    m = Minuit(quad, values=np.array([0.5, -0.5]))
+    assert m.values["x"] == 0.5
+    assert m.values["y"] == -0.5
+
+
+def test_init_rejects_values_and_positional():
+    with pytest.raises(ValueError):
+        Minuit(quad, 0.0, 0.0, values=np.array([0.5, -0.5]))
+
+
+def test_values_array_roundtrip():
+    m = Minuit(quad, x=0.0, y=0.0)
+    m.set_values_array(np.array([3.0, 4.0]))
+    arr = m.get_values_array()
+    assert isinstance(arr, np.ndarray)
+    assert arr.shape == (2,)
+    assert np.allclose(arr, [3.0, 4.0])
+
+    m.migrad()
+    assert np.allclose(m.get_values_array(), [1.0, 2.0], atol=1e-3)
+
+
+def test_set_values_array_checks_length():
+    m = Minuit(quad, x=0.0, y=0.0)
+    with pytest.raises(ValueError):
+        m.set_values_array(np.array([1.0]))
@@ -48,10 +48,26 @@ def test_mncontour_is_closed_nx2():
     assert np.allclose(pts[0], pts[-1])
 
 
-def test_mncontour_cl_not_implemented():
+def test_mncontour_cl_sets_chi2_level():
     m = fitted()
-    with pytest.raises(NotImplementedError):
-        m.mncontour("x", "y", cl=0.9)
+    pts = m.mncontour("x", "y", cl=0.9, numpoints=12)
+    assert pts.shape == (13, 2)
+    # chi2 quantile for 2 dof at 90%: -2 ln(0.1) ~ 4.605
+    level = -2.0 * np.log(0.1)
+    fs = (pts[:, 0] - 1.0) ** 2 + (pts[:, 1] - 2.0) ** 2
+    assert np.allclose(fs, level, rtol=0.1)
+    with pytest.raises(ValueError):
+        m.mncontour("x", "y", cl=1.5)
+
+
+def test_mncontour_defaults_to_20_points_at_68_percent():
+    m = fitted()
+    pts = m.mncontour("x", "y")
+    assert pts.shape == (21, 2)
+    # chi2 quantile for 2 dof at 68%: -2 ln(0.32) ~ 2.279
+    level = -2.0 * np.log(0.32)
+    fs = (pts[:, 0] - 1.0) ** 2 + (pts[:, 1] - 2.0) ** 2
+    assert np.allclose(fs, level, rtol=0.1)
 
 
 def test_contour_grid_shapes():
@@ -92,3 +108,13 @@ def test_scan_uses_limits_and_includes_boundaries():
     except AssertionError:
         print("minuit2 scan values", m.values.to_dict(), "fval", m.fval)
         raise
+
+
+def test_mnprofile_accepts_bins_and_explicit_range():
+    m = fitted()
+    xs, fs, ok = m.mnprofile("x", bins=5, bound=(0.0, 2.0))
+    assert xs.shape == (5,) and fs.shape == (5,) and ok.shape == (5,)
+    assert np.allclose(xs, [0.0, 0.5, 1.0, 1.5, 2.0])
+    # y is re-minimized at each point, so the profile is exactly (x - 1)^2.
+    assert np.allclose(fs, (xs - 1.0) ** 2, atol=1e-6)
+    assert ok.all()
@@ -72,3 +72,83 @@ def test_hesse_then_minos_populates_merrors():
     assert ex.is_valid
     assert ex.lower < 0.0 < ex.upper  # iminuit sign convention
     assert ex.upper == abs(ex.lower) or ex.upper > 0.0
+
+
+def test_covariance_includes_fixed_parameters():
+    def f3(x, y, z):
+        return (x - 1.0) ** 2 + (y - 2.0) ** 2 + (z - 3.0) ** 2
+
+    m = Minuit(f3, x=0.0, y=0.0, z=0.0)
+    m.fixed["y"] = True
+    m.migrad().hesse()
+    cov = m.covariance
+    assert len(cov) == 3 and all(len(row) == 3 for row in cov)
+    assert cov[1] == [0.0, 0.0, 0.0]
+    assert [row[1] for row in cov] == [0.0, 0.0, 0.0]
+    assert abs(cov[2][2] - 1.0) < 1e-3
+    var = m.covariance_variable
+    assert len(var) == 2
+    assert var[0][0] == cov[0][0] and var[1][1] == cov[2][2]
+
+
+def test_keyword_init_assigns_errors():
+    m = Minuit(quad, x=5.0, y=0.0, error_y=0.3)
+    assert m.values["x"] == 5.0
+    assert abs(m.errors["x"] - 0.5) < 1e-12  # 10% of |value|
+    assert m.errors["y"] == 0.3  # explicit error_<name>
+
+
+def test_errordef_detected_from_fcn_attribute():
+    def nll(x, y):
+        return quad(x, y)
+
+    nll.errordef = 0.5
+    assert Minuit(nll, x=0.0, y=0.0).errordef == 0.5
+    assert Minuit(quad, x=0.0, y=0.0).errordef == 1.0
+
+
+def test_params_df_and_correlation_matrix():
+    m = Minuit(lambda x, y: (x - 1.0) ** 2 + (y - 2.0) ** 2 + x * y, x=0.0, y=0.0)
+    m.limits["x"] = (-5.0, 5.0)
+    m.migrad().hesse()
+    df = m.params_df()
+    assert df["name"] == ["x", "y"]
+    assert df["lower"] == [-5.0, None] and df["upper"] == [5.0, None]
+    assert df["fixed"] == [False, False]
+    assert len(df["value"]) == len(df["error"]) == 2
+    corr = m.matrix()
+    assert abs(corr[0][0] - 1.0) < 1e-9 and abs(corr[1][1] - 1.0) < 1e-9
+    assert abs(corr[0][1] - corr[1][0]) < 1e-12
+    assert corr[0][1] < 0.0  # positive x*y coupling anti-correlates x and y
+
+
+def test_context_manager_resets_call_counter():
+    with Minuit(quad, x=0.0, y=0.0) as m:
+        m.migrad()
+        assert m.nfcn > 0
+    assert m.valid
+    assert m.nfcn == 0
+    m.migrad()
+    assert m.nfcn > 0
+
+
+def test_context_manager_is_quiet_on_success(capsys):
+    with Minuit(quad, x=0.0, y=0.0) as m:
+        m.migrad()
+    assert capsys.readouterr().out == ""
+
+
+def test_strategy_accepts_names():
+    m = Minuit(quad, x=0.0, y=0.0)
+    m.strategy = "high"
+    assert m.strategy == 2
+    m.strategy = "low"
+    assert m.strategy == 0
+    for bad in ("fast", -1, 3, 1.5):
+        try:
+            m.strategy = bad
+            raised = False
+        except ValueError:
+            raised = True
+        assert raised
+    assert m.strategy == 0
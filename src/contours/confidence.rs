@@ -0,0 +1,162 @@
+//! Conversions between `Up` values and confidence levels.
+//!
+//! A region `F <= Fmin + Up` with `Up = q * error_def` covers the chi-squared
+//! probability `P(q; ndof)`, where `ndof` is the number of parameters
+//! profiled jointly (1 for MINOS intervals, 2 for contours).
+
+/// Confidence level of a 2D contour drawn at `up`.
+///
+/// Returns the chi-squared CDF with 2 degrees of freedom at
+/// `up / error_def`, i.e. `1 - exp(-up / (2 * error_def))`. A 1σ contour
+/// (`up == error_def`) covers about 39.3%.
+pub fn confidence_level_from_up(up: f64, error_def: f64) -> f64 {
+    chi2_cdf(up / error_def, 2)
+}
+
+/// `Up` value whose region covers `cl` for `ndof` jointly profiled parameters.
+///
+/// Inverts the chi-squared CDF and scales by `error_def`, so
+/// `up_from_confidence_level(0.6827, 1.0, 1)` is about 1 and
+/// `up_from_confidence_level(0.95, 1.0, 2)` is about 5.99. Returns NaN when
+/// `cl` is outside `(0, 1)` or `ndof` is zero.
+pub fn up_from_confidence_level(cl: f64, error_def: f64, ndof: usize) -> f64 {
+    if !(cl > 0.0 && cl < 1.0) || ndof == 0 {
+        return f64::NAN;
+    }
+    chi2_quantile(cl, ndof) * error_def
+}
+
+/// Chi-squared CDF: the regularized lower incomplete gamma `P(ndof/2, x/2)`.
+pub(crate) fn chi2_cdf(x: f64, ndof: usize) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    gamma_p(0.5 * ndof as f64, 0.5 * x)
+}
+
+/// Chi-squared quantile by bisection on the monotone CDF.
+fn chi2_quantile(p: f64, ndof: usize) -> f64 {
+    let mut hi = ndof as f64 + 1.0;
+    while chi2_cdf(hi, ndof) < p {
+        hi *= 2.0;
+    }
+    let mut lo = 0.0;
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if chi2_cdf(mid, ndof) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo <= 1e-14 * hi {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Regularized lower incomplete gamma function `P(a, x)`.
+///
+/// Series expansion for `x < a + 1`, Lentz continued fraction otherwise.
+fn gamma_p(a: f64, x: f64) -> f64 {
+    let ln_prefactor = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut ap = a;
+        for _ in 0..500 {
+            ap += 1.0;
+            term *= x / ap;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-16 {
+                break;
+            }
+        }
+        sum * ln_prefactor.exp()
+    } else {
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..500 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-16 {
+                break;
+            }
+        }
+        1.0 - ln_prefactor.exp() * h
+    }
+}
+
+/// Natural log of the gamma function (Lanczos approximation, g = 7).
+fn ln_gamma(x: f64) -> f64 {
+    const COEF: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection: Γ(x) Γ(1 - x) = π / sin(πx).
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEF[1..]
+        .iter()
+        .enumerate()
+        .fold(COEF[0], |acc, (i, c)| acc + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_sigma_levels() {
+        // 1σ in one dimension is Δχ² = 1.
+        assert!((chi2_cdf(1.0, 1) - 0.682_689_492_137_086).abs() < 1e-10);
+        assert!((up_from_confidence_level(0.682_689_492_137_086, 1.0, 1) - 1.0).abs() < 1e-8);
+        assert!((confidence_level_from_up(1.0, 1.0) - (1.0 - (-0.5f64).exp())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn known_quantiles_and_error_def_scaling() {
+        assert!((up_from_confidence_level(0.95, 1.0, 1) - 3.841_458_820_694_124).abs() < 1e-8);
+        assert!((up_from_confidence_level(0.95, 1.0, 2) - 5.991_464_547_107_979).abs() < 1e-8);
+        assert!((up_from_confidence_level(0.99, 1.0, 5) - 15.086_272_469_388_99).abs() < 1e-7);
+        // Negative log-likelihood: error_def = 0.5 halves Up.
+        assert!(
+            (up_from_confidence_level(0.95, 0.5, 2) - 0.5 * 5.991_464_547_107_979).abs() < 1e-8
+        );
+        assert!((confidence_level_from_up(0.5 * 5.991_464_547_107_979, 0.5) - 0.95).abs() < 1e-12);
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert!(up_from_confidence_level(1.0, 1.0, 2).is_nan());
+        assert!(up_from_confidence_level(0.0, 1.0, 2).is_nan());
+        assert!(up_from_confidence_level(0.5, 1.0, 0).is_nan());
+        assert_eq!(confidence_level_from_up(0.0, 1.0), 0.0);
+    }
+}
@@ -3,21 +3,36 @@
 //! Computes points tracing a contour where `F(x, y) = Fmin + Up` in the
 //! `(par_x, par_y)` plane.
 
+pub mod confidence;
 pub mod contours_error;
 
+pub use confidence::{confidence_level_from_up, up_from_confidence_level};
 pub use contours_error::ContoursError;
 
+use std::borrow::Cow;
+
+use nalgebra::{Matrix3, Vector3};
+
+use crate::application::default_max_fcn;
 use crate::fcn::FCN;
 use crate::minimum::FunctionMinimum;
-use crate::minos::MnMinos;
 use crate::minos::minos_error::MinosError;
+use crate::minos::{MnCross, MnMinos, function_cross};
+use crate::parameter::MinuitParameter;
 use crate::strategy::MnStrategy;
 
+/// Contour points used by [`MnContours::ellipse_approx`].
+pub const ELLIPSE_FIT_POINTS: usize = 20;
+
+/// Maximum bisection depth per original pair in [`MnContours::refine`].
+pub const REFINE_MAX_DEPTH: usize = 6;
+
 /// Compute 2D confidence contours.
 pub struct MnContours<'a> {
     fcn: &'a dyn FCN,
-    minimum: &'a FunctionMinimum,
+    minimum: Cow<'a, FunctionMinimum>,
     strategy: MnStrategy,
+    tolerance: f64,
 }
 
 impl<'a> MnContours<'a> {
@@ -25,8 +40,9 @@ impl<'a> MnContours<'a> {
     pub fn new(fcn: &'a dyn FCN, minimum: &'a FunctionMinimum) -> Self {
         Self {
             fcn,
-            minimum,
+            minimum: Cow::Borrowed(minimum),
             strategy: MnStrategy::default(),
+            tolerance: 0.1,
         }
     }
 
@@ -36,16 +52,37 @@ impl<'a> MnContours<'a> {
         self
     }
 
+    /// Set the crossing tolerance, relative to Up (default 0.1).
+    ///
+    /// Used for the MINOS cardinal points and for every further contour
+    /// point; 0.01 gives publication-quality contours at a higher call cost.
+    pub fn with_tolerance(mut self, tol: f64) -> Self {
+        self.tolerance = tol;
+        self
+    }
+
+    /// Override the error definition Up used for the contour.
+    ///
+    /// Replaces `minimum.up()` for the MINOS crossings and every contour
+    /// point, e.g. `with_up(2.3)` for the 68% region of two parameters of a
+    /// chi-square FCN. The caller's minimum is left untouched.
+    pub fn with_up(mut self, up: f64) -> Self {
+        self.minimum.to_mut().set_error_def(up);
+        self
+    }
+
     /// Compute contour points for parameters `par_x` and `par_y`.
     ///
     /// Returns `npoints` points tracing the F = Fmin + Up contour.
     /// Minimum 4 points (the MINOS cardinal points).
+    ///
+    /// The four cardinal points come from the MINOS crossings, with the other
+    /// coordinate taken from the re-minimized state at each crossing. Further
+    /// points are added by bisecting the largest gap between neighbours and
+    /// searching outward along the gap's normal for the profile crossing,
+    /// re-minimizing all other parameters at each trial point.
     pub fn points(&self, par_x: usize, par_y: usize, npoints: usize) -> Vec<(f64, f64)> {
         let npoints = npoints.max(4);
-        let nvar = self.minimum.n_variable_params();
-        let _maxcalls = 100 * (npoints + 5) * (nvar + 1);
-
-        let up = self.minimum.up();
         let user_state = self.minimum.user_state();
 
         let (x_minos, y_minos) = self.minos_errors(par_x, par_y);
@@ -58,21 +95,26 @@ impl<'a> MnContours<'a> {
         let x_val = user_state.parameter(par_x).value();
         let y_val = user_state.parameter(par_y).value();
 
-        // x_upper: fix x at upper, find y
-        // x_lower: fix x at lower, find y
-        // y_upper: fix y at upper, find x
-        // y_lower: fix y at lower, find x
         let x_up = x_val + x_minos.upper_error();
         let x_lo = x_val + x_minos.lower_error(); // lower_error is negative
         let y_up = y_val + y_minos.upper_error();
         let y_lo = y_val + y_minos.lower_error();
 
-        // The 4 cardinal points (approximate — on the contour boundary)
+        // At the x crossings the profile has re-minimized y (and vice versa),
+        // so take the other coordinate from the crossing state.
+        let at_cross = |cross: &MnCross, par: usize, fallback: f64| {
+            if cross.state().len() > par {
+                cross.state().parameter(par).value()
+            } else {
+                fallback
+            }
+        };
+
         let mut pts = vec![
-            (x_up, y_val), // right
-            (x_val, y_up), // top
-            (x_lo, y_val), // left
-            (x_val, y_lo), // bottom
+            (x_up, at_cross(x_minos.upper(), par_y, y_val)), // right
+            (at_cross(y_minos.upper(), par_x, x_val), y_up), // top
+            (x_lo, at_cross(x_minos.lower(), par_y, y_val)), // left
+            (at_cross(y_minos.lower(), par_x, x_val), y_lo), // bottom
         ];
 
         if npoints <= 4 {
@@ -91,12 +133,10 @@ impl<'a> MnContours<'a> {
             1.0
         };
 
-        // Add more points by bisecting largest gaps
         let remaining = npoints - 4;
-        for _ in 0..remaining {
-            if pts.len() < 2 {
-                break;
-            }
+        let mut attempts = 0;
+        while pts.len() < npoints && attempts < 2 * remaining {
+            attempts += 1;
 
             // Find largest gap (in scaled distance)
             let mut max_dist = 0.0_f64;
@@ -116,59 +156,206 @@ impl<'a> MnContours<'a> {
             let mid_x = 0.5 * (pts[max_idx].0 + pts[j].0);
             let mid_y = 0.5 * (pts[max_idx].1 + pts[j].1);
 
-            // Perpendicular direction from center
-            let dx = pts[j].0 - pts[max_idx].0;
-            let dy = pts[j].1 - pts[max_idx].1;
-            // Normal to the gap segment, pointing outward from minimum
-            let nx = -dy * scalx;
-            let ny = dx * scaly;
-            let norm = (nx * nx + ny * ny).sqrt();
-            if norm < 1e-15 {
+            // Unit step in scaled space: about the size of the contour.
+            let Some(dir) = outward_normal(pts[max_idx], pts[j], (x_val, y_val), (scalx, scaly))
+            else {
+                break;
+            };
+
+            let Some(point) = self.contour_crossing(par_x, par_y, (mid_x, mid_y), dir) else {
+                // No crossing (e.g. contour cut open by a limit): keep the
+                // midpoint so the gap is not retried forever.
+                pts.insert(max_idx + 1, (mid_x, mid_y));
                 continue;
-            }
+            };
+
+            pts.insert(max_idx + 1, point);
+        }
 
-            // Try to find a contour point near the midpoint.
-            // Use MnFunctionCross along the perpendicular from the minimum.
-            // For simplicity, we do a scan along the perpendicular direction
-            // from the center (x_val, y_val) through the midpoint.
-            let dir_x = mid_x - x_val;
-            let dir_y = mid_y - y_val;
+        pts
+    }
 
-            // Evaluate function at midpoint to see if we're close to contour
-            let nparams = user_state.len();
-            let mut pars: Vec<f64> = (0..nparams)
-                .map(|i| user_state.parameter(i).value())
-                .collect();
-            pars[par_x] = mid_x;
-            pars[par_y] = mid_y;
-            let f_mid = self.fcn.value(&pars);
-
-            // Adjust: scale to hit the contour F = fmin + up
-            let fmin = self.minimum.fval();
-            let target = fmin + up;
-            let ratio = if (f_mid - fmin).abs() > 1e-15 {
-                (target / (f_mid - fmin)).sqrt()
-            } else {
-                1.0
-            };
+    /// Insert points into a coarse contour until it follows the true one.
+    ///
+    /// `points` is taken as a closed loop, e.g. from [`MnContours::points`]
+    /// or [`MnContours::points_ordered`] (a repeated closing point is kept).
+    /// For each pair of neighbours the midpoint is moved onto the contour
+    /// along the pair's normal; if it moved by more than `target_accuracy`,
+    /// measured in units of the parameter errors, the snapped point is
+    /// inserted and both halves are refined in turn, at most
+    /// [`REFINE_MAX_DEPTH`] times per original pair.
+    pub fn refine(
+        &self,
+        points: Vec<(f64, f64)>,
+        par_x: usize,
+        par_y: usize,
+        target_accuracy: f64,
+    ) -> Vec<(f64, f64)> {
+        let closed = points.len() > 1 && points.first() == points.last();
+        let mut pts = points;
+        if closed {
+            pts.pop();
+        }
+        if pts.len() < 2 {
+            return pts;
+        }
 
-            let new_x = x_val + dir_x * ratio;
-            let new_y = y_val + dir_y * ratio;
+        let user_state = self.minimum.user_state();
+        let (px, py) = (user_state.parameter(par_x), user_state.parameter(par_y));
+        let segment = RefineSegment {
+            par_x,
+            par_y,
+            center: (px.value(), py.value()),
+            scale: (1.0 / px.error(), 1.0 / py.error()),
+            target_accuracy,
+        };
 
-            // Check it's a reasonable point
-            let seg_dist =
-                ((new_x - pts[max_idx].0).powi(2) + (new_y - pts[max_idx].1).powi(2)).sqrt();
-            if seg_dist < 1e-10 {
-                continue;
-            }
+        let mut refined = Vec::with_capacity(2 * pts.len());
+        for i in 0..pts.len() {
+            refined.push(pts[i]);
+            let next = pts[(i + 1) % pts.len()];
+            self.refine_between(&segment, pts[i], next, REFINE_MAX_DEPTH, &mut refined);
+        }
+        if closed {
+            refined.push(refined[0]);
+        }
+        refined
+    }
+
+    /// Push the refined points strictly between `a` and `b` onto `out`.
+    fn refine_between(
+        &self,
+        segment: &RefineSegment,
+        a: (f64, f64),
+        b: (f64, f64),
+        depth: usize,
+        out: &mut Vec<(f64, f64)>,
+    ) {
+        if depth == 0 {
+            return;
+        }
+        let (sx, sy) = segment.scale;
+        let Some(dir) = outward_normal(a, b, segment.center, segment.scale) else {
+            return;
+        };
+        let mid = (0.5 * (a.0 + b.0), 0.5 * (a.1 + b.1));
+        let Some(p) = self.contour_crossing(segment.par_x, segment.par_y, mid, dir) else {
+            return;
+        };
+        if ((p.0 - mid.0) * sx).hypot((p.1 - mid.1) * sy) <= segment.target_accuracy {
+            return;
+        }
+        self.refine_between(segment, a, p, depth - 1, out);
+        out.push(p);
+        self.refine_between(segment, p, b, depth - 1, out);
+    }
+
+    /// Contour points at the `n_sigma` level.
+    ///
+    /// Runs [`MnContours::points`] with `Up = n_sigma² * error_def`, e.g.
+    /// `points_sigma(x, y, 2.0, 20)` for the 2σ contour.
+    pub fn points_sigma(
+        &self,
+        par_x: usize,
+        par_y: usize,
+        n_sigma: f64,
+        npoints: usize,
+    ) -> Vec<(f64, f64)> {
+        MnContours {
+            fcn: self.fcn,
+            minimum: self.minimum.clone(),
+            strategy: self.strategy,
+            tolerance: self.tolerance,
+        }
+        .with_up(n_sigma * n_sigma * self.fcn.error_def())
+        .points(par_x, par_y, npoints)
+    }
 
-            // Insert after max_idx
-            pts.insert(max_idx + 1, (new_x, new_y));
+    /// Contour points sorted by polar angle around the minimum, closed.
+    ///
+    /// Runs [`MnContours::points`], orders the result counter-clockwise by
+    /// angle around `(x_min, y_min)` so the polygon edges do not cross, and
+    /// repeats the first point at the end.
+    pub fn points_ordered(&self, par_x: usize, par_y: usize, npoints: usize) -> Vec<(f64, f64)> {
+        let mut pts = self.points(par_x, par_y, npoints);
+        if pts.is_empty() {
+            return pts;
         }
 
+        let user_state = self.minimum.user_state();
+        let x_min = user_state.parameter(par_x).value();
+        let y_min = user_state.parameter(par_y).value();
+        let angle = |p: &(f64, f64)| (p.1 - y_min).atan2(p.0 - x_min);
+        pts.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+
+        pts.push(pts[0]);
         pts
     }
 
+    /// Area enclosed by the contour, by the Shoelace formula.
+    ///
+    /// Uses the closed polygon from [`MnContours::points_ordered`]; zero when
+    /// fewer than three points were found.
+    pub fn area(&self, par_x: usize, par_y: usize, npoints: usize) -> f64 {
+        let pts = self.points_ordered(par_x, par_y, npoints);
+        if pts.len() < 4 {
+            return 0.0;
+        }
+        let twice: f64 = pts
+            .windows(2)
+            .map(|w| w[0].0 * w[1].1 - w[1].0 * w[0].1)
+            .sum();
+        0.5 * twice.abs()
+    }
+
+    /// Ellipse fitted to the contour: `(semi_major, semi_minor, angle)`.
+    ///
+    /// Fits `a*dx^2 + b*dx*dy + c*dy^2 = 1` by least squares to
+    /// [`ELLIPSE_FIT_POINTS`] contour points, with `(dx, dy)` measured from
+    /// the minimum. `angle` is the major axis direction from the `par_x` axis,
+    /// in `(-pi/2, pi/2]`. All NaN when the points do not describe an ellipse.
+    pub fn ellipse_approx(&self, par_x: usize, par_y: usize) -> (f64, f64, f64) {
+        let pts = self.points(par_x, par_y, ELLIPSE_FIT_POINTS);
+        if pts.len() < 3 {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+        let user_state = self.minimum.user_state();
+        let x_min = user_state.parameter(par_x).value();
+        let y_min = user_state.parameter(par_y).value();
+
+        let mut normal = Matrix3::zeros();
+        let mut rhs = Vector3::zeros();
+        for &(x, y) in &pts {
+            let (dx, dy) = (x - x_min, y - y_min);
+            let row = Vector3::new(dx * dx, dx * dy, dy * dy);
+            normal += row * row.transpose();
+            rhs += row;
+        }
+        let Some(coef) = normal.lu().solve(&rhs) else {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        };
+
+        // Eigenvalues of [[a, b/2], [b/2, c]]; the smaller one belongs to the
+        // major axis.
+        let (a, h, c) = (coef[0], 0.5 * coef[1], coef[2]);
+        let mean = 0.5 * (a + c);
+        let radius = (0.25 * (a - c) * (a - c) + h * h).sqrt();
+        let (lambda_major, lambda_minor) = (mean - radius, mean + radius);
+        if lambda_major <= 0.0 {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+
+        let mut angle = 0.5 * (2.0 * h).atan2(a - c) + std::f64::consts::FRAC_PI_2;
+        if angle > std::f64::consts::FRAC_PI_2 {
+            angle -= std::f64::consts::PI;
+        }
+        (
+            lambda_major.sqrt().recip(),
+            lambda_minor.sqrt().recip(),
+            angle,
+        )
+    }
+
     /// Compute full contour with MINOS errors for both axes.
     pub fn contour(&self, par_x: usize, par_y: usize, npoints: usize) -> ContoursError {
         let (x_minos, y_minos) = self.minos_errors(par_x, par_y);
@@ -186,8 +373,142 @@ impl<'a> MnContours<'a> {
     }
 
     fn minos_errors(&self, par_x: usize, par_y: usize) -> (MinosError, MinosError) {
-        let minos = MnMinos::new(self.fcn, self.minimum).with_strategy(self.strategy.strategy());
+        let minos = MnMinos::new(self.fcn, &self.minimum)
+            .with_strategy(self.strategy.strategy())
+            .with_tolerance(self.tolerance);
 
         (minos.minos_error(par_x), minos.minos_error(par_y))
     }
+
+    /// Profile function value with `par_x`, `par_y` held at `(x, y)`.
+    ///
+    /// All other variable parameters are re-minimized; with none left the
+    /// FCN is evaluated directly. Returns `None` outside the parameter limits
+    /// or when the re-minimization fails.
+    fn profile_value(&self, par_x: usize, par_y: usize, x: f64, y: f64) -> Option<f64> {
+        let user_state = self.minimum.user_state();
+        if !within_limits(user_state.parameter(par_x), x)
+            || !within_limits(user_state.parameter(par_y), y)
+        {
+            return None;
+        }
+
+        let others_free = (0..user_state.len())
+            .any(|i| i != par_x && i != par_y && !user_state.parameter(i).is_fixed());
+        if !others_free {
+            let mut pars: Vec<f64> = (0..user_state.len())
+                .map(|i| user_state.parameter(i).value())
+                .collect();
+            pars[par_x] = x;
+            pars[par_y] = y;
+            return Some(self.fcn.value(&pars));
+        }
+
+        let mgr_strategy = MnStrategy::new(self.strategy.strategy().saturating_sub(1));
+        let nvar = self.minimum.n_variable_params();
+        let result = function_cross::run_migrad_with_fixed(
+            self.fcn,
+            &self.minimum,
+            &[(par_x, x), (par_y, y)],
+            &mgr_strategy,
+            0.5 * self.tolerance,
+            default_max_fcn(nvar),
+        );
+        result.is_valid().then(|| result.fval())
+    }
+
+    /// Find where the profile reaches `fmin + Up` along the ray
+    /// `start + a * dir`, by bracketing and then regula falsi.
+    fn contour_crossing(
+        &self,
+        par_x: usize,
+        par_y: usize,
+        start: (f64, f64),
+        dir: (f64, f64),
+    ) -> Option<(f64, f64)> {
+        let target = self.minimum.fval() + self.minimum.up();
+        let ftol = self.tolerance * self.minimum.up();
+        let at = |a: f64| (start.0 + a * dir.0, start.1 + a * dir.1);
+        let eval = |a: f64| {
+            let (x, y) = at(a);
+            self.profile_value(par_x, par_y, x, y).map(|f| f - target)
+        };
+
+        // Bracket the crossing: step outward (or inward when the start is
+        // already outside the contour) with growing steps.
+        let g0 = eval(0.0)?;
+        let sign = if g0 < 0.0 { 1.0 } else { -1.0 };
+        let (mut a_in, mut g_in) = (0.0, g0);
+        let mut step = 0.1;
+        let (mut a_out, mut g_out) = loop {
+            let a = sign * step;
+            let g = eval(a)?;
+            if g * g0 <= 0.0 {
+                break (a, g);
+            }
+            (a_in, g_in) = (a, g);
+            step *= 2.0;
+            if step > 100.0 {
+                return None;
+            }
+        };
+
+        // Illinois variant of regula falsi.
+        let mut side = 0;
+        for _ in 0..50 {
+            let a = (a_in * g_out - a_out * g_in) / (g_out - g_in);
+            let g = eval(a)?;
+            if g.abs() < ftol {
+                return Some(at(a));
+            }
+            if g * g_out < 0.0 {
+                (a_in, g_in) = (a_out, g_out);
+                (a_out, g_out) = (a, g);
+                side = 0;
+            } else {
+                (a_out, g_out) = (a, g);
+                if side == -1 {
+                    g_in *= 0.5;
+                }
+                side = -1;
+            }
+        }
+        None
+    }
+}
+
+/// Settings shared by the recursive steps of [`MnContours::refine`].
+struct RefineSegment {
+    par_x: usize,
+    par_y: usize,
+    center: (f64, f64),
+    scale: (f64, f64),
+    target_accuracy: f64,
+}
+
+/// Unit normal (in scaled space) to the segment `a`-`b`, mapped back to
+/// parameter space and oriented away from `center`. `None` for a
+/// degenerate segment.
+fn outward_normal(
+    a: (f64, f64),
+    b: (f64, f64),
+    center: (f64, f64),
+    (scalx, scaly): (f64, f64),
+) -> Option<(f64, f64)> {
+    let mid = (0.5 * (a.0 + b.0), 0.5 * (a.1 + b.1));
+    let sdx = (b.0 - a.0) * scalx;
+    let sdy = (b.1 - a.1) * scaly;
+    let mut nx = sdy / scalx;
+    let mut ny = -sdx / scaly;
+    if nx * (mid.0 - center.0) + ny * (mid.1 - center.1) < 0.0 {
+        nx = -nx;
+        ny = -ny;
+    }
+    let norm = ((nx * scalx).powi(2) + (ny * scaly).powi(2)).sqrt();
+    (norm >= 1e-15).then(|| (nx / norm, ny / norm))
+}
+
+fn within_limits(p: &MinuitParameter, value: f64) -> bool {
+    !(p.has_lower_limit() && value < p.lower_limit()
+        || p.has_upper_limit() && value > p.upper_limit())
 }
@@ -86,6 +86,14 @@ pub trait FCNGradient: FCN {
     fn gradient(&self, par: &[f64]) -> Vec<f64>;
 }
 
+/// Chi-square FCN that exposes its per-observation residuals.
+///
+/// `value()` is expected to equal the sum of the squared residuals.
+pub trait FCNResiduals: FCN {
+    /// Normalized residuals `(observation - model) / sigma`, one per point.
+    fn residuals(&self, par: &[f64]) -> Vec<f64>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,15 +3,19 @@
 //! The `GradientCalculator` trait defines the interface. Concrete impls:
 //! - `InitialGradientCalculator`: computes a first gradient estimate from step sizes
 //! - `Numerical2PGradientCalculator`: two-point central differences
+//! - `Numerical4PGradientCalculator` / `Numerical5PGradientCalculator`:
+//!   higher-order stencils, selected with [`GradientMethod`]
 //! - `AnalyticalGradientCalculator`: user-provided analytical gradients
 
 pub mod analytical;
 pub mod initial;
 pub mod numerical;
+pub mod stencil;
 
 pub use analytical::AnalyticalGradientCalculator;
 pub use initial::InitialGradientCalculator;
 pub use numerical::Numerical2PGradientCalculator;
+pub use stencil::{GradientMethod, Numerical4PGradientCalculator, Numerical5PGradientCalculator};
 
 use crate::minimum::gradient::FunctionGradient;
 use crate::minimum::parameters::MinimumParameters;
@@ -1,7 +1,9 @@
 //! Core Hesse algorithm: computes the full Hessian matrix by finite differences.
 //!
 //! Steps:
-//! 1. Diagonal elements via 5-point refinement
+//! 0. If the FCN provides an analytical Hessian and no variable parameter is
+//!    limited, use it and skip to step 4
+//! 1. Diagonal elements via 5-point refinement, or from the FCN's `g2()`
 //! 2. Gradient refinement using Hessian info (if strategy > 0)
 //! 3. Off-diagonal elements via cross-derivatives
 //! 4. Make positive-definite
@@ -9,6 +11,7 @@
 
 use nalgebra::{DMatrix, DVector};
 
+use crate::gradient::analytical::AnalyticalGradientCalculator;
 use crate::minimum::error::{ErrorMatrixStatus, MinimumError};
 use crate::minimum::gradient::FunctionGradient;
 use crate::minimum::state::MinimumState;
@@ -25,6 +28,8 @@ pub struct HesseResult {
     pub hesse_failed: bool,
     pub invert_failed: bool,
     pub made_pos_def: bool,
+    /// Function evaluations used by this calculation.
+    pub ncalls: usize,
 }
 
 /// Run the full Hesse algorithm.
@@ -38,6 +43,27 @@ pub fn calculate(
     trafo: &MnUserTransformation,
     strategy: &MnStrategy,
     maxcalls: usize,
+) -> HesseResult {
+    calculate_with(fcn, state, trafo, strategy, maxcalls, false, None)
+}
+
+/// Run the Hesse algorithm, optionally skipping the off-diagonal elements.
+///
+/// With `diagonal_only`, or with the low strategy (0), Step 3 is skipped and
+/// the Hessian is taken to be diagonal; the resulting error matrix is flagged
+/// [`ErrorMatrixStatus::DiagonalOnly`]. An analytical Hessian needs no cross
+/// derivatives, so only an explicit `diagonal_only` reduces it. `max_cross_calls` caps the function
+/// evaluations spent in Step 3; once it is exhausted the remaining
+/// off-diagonal elements stay zero and the matrix is flagged
+/// [`ErrorMatrixStatus::CrossTermsTruncated`].
+pub fn calculate_with(
+    fcn: &MnFcn,
+    state: &MinimumState,
+    trafo: &MnUserTransformation,
+    strategy: &MnStrategy,
+    maxcalls: usize,
+    diagonal_only: bool,
+    max_cross_calls: Option<usize>,
 ) -> HesseResult {
     let n = trafo.variable_parameters();
     let eps2 = trafo.precision().eps2();
@@ -45,6 +71,26 @@ pub fn calculate(
     let amin = state.fval();
 
     let x = state.parameters().vec().clone();
+
+    // --- Step 0: Analytical Hessian fast path ---
+    // Costs no cross-derivative calls, so only an explicit `diagonal_only`
+    // (not the low strategy) drops the off-diagonal terms.
+    if let Some(mut hessian) = analytical_hessian(fcn, trafo, &x) {
+        if diagonal_only {
+            hessian = DMatrix::from_diagonal(&hessian.diagonal());
+        }
+        let g = state.gradient();
+        let g2 = hessian.diagonal();
+        let gradient = FunctionGradient::new(g.grad().clone(), g2, g.gstep().clone());
+        let status = if diagonal_only {
+            ErrorMatrixStatus::DiagonalOnly
+        } else {
+            ErrorMatrixStatus::Accurate
+        };
+        return finish(fcn, state, trafo, hessian, gradient, false, status);
+    }
+
+    let diagonal_only = diagonal_only || strategy.strategy() == 0;
     let ncycles = strategy.hess_ncycles();
     let hess_step_tol = strategy.hess_step_tol();
     let hess_g2_tol = strategy.hess_g2_tol();
@@ -56,6 +102,7 @@ pub fn calculate(
     let mut grad = g.grad().clone();
 
     // --- Step 1: Diagonal Hessian elements ---
+    let analytical_g2 = analytical_g2(fcn, trafo, &x);
     let mut hessian_g2 = DVector::zeros(n);
     let mut hessian_gstep = DVector::zeros(n);
     let mut yy = DVector::zeros(n);
@@ -73,6 +120,27 @@ pub fn calculate(
 
         let dmin = 8.0 * eps2 * (xi.abs() + eps2);
         let aimsag = eps2.sqrt() * (amin.abs() + up);
+
+        // Fast path: take g2 from the FCN and only probe the step that the
+        // off-diagonal terms need.
+        if let Some(g2i) = analytical_g2
+            .as_ref()
+            .and_then(|g| g[i])
+            .filter(|g| g.is_finite() && *g != 0.0)
+        {
+            let d = (2.0 * aimsag / g2i.abs()).sqrt().max(dmin);
+            if !diagonal_only {
+                let mut xp = x.clone();
+                xp[i] = xi + d;
+                yy[i] = fcn.call(xp.as_slice());
+            }
+            gstep[i] = d;
+            hessian_g2[i] = g2i;
+            hessian_gstep[i] = d;
+            g2[i] = g2i;
+            continue;
+        }
+
         let mut d = gstep[i].abs().max(dmin);
         let mut g2i = g2[i];
 
@@ -132,6 +200,7 @@ pub fn calculate(
                     hesse_failed: true,
                     invert_failed: false,
                     made_pos_def: false,
+                    ncalls: fcn.num_of_calls(),
                 };
             }
 
@@ -185,11 +254,19 @@ pub fn calculate(
     }
 
     // Off-diagonal: H(i,j) = (f(x+di*ei+dj*ej) + f0 - f(x+di*ei) - f(x+dj*ej)) / (di*dj)
-    for i in 0..n {
+    // Skipped in diagonal-only mode and for the low strategy.
+    let ncross = if diagonal_only { 0 } else { n };
+    let cross_start = fcn.num_of_calls();
+    let mut cross_truncated = false;
+    for i in 0..ncross {
         for j in (i + 1)..n {
             if fcn.num_of_calls() >= maxcalls {
                 break;
             }
+            if max_cross_calls.is_some_and(|max| fcn.num_of_calls() - cross_start >= max) {
+                cross_truncated = true;
+                break;
+            }
 
             let di = hessian_gstep[i];
             let dj = hessian_gstep[j];
@@ -205,6 +282,107 @@ pub fn calculate(
         }
     }
 
+    let status = if diagonal_only {
+        ErrorMatrixStatus::DiagonalOnly
+    } else if cross_truncated {
+        ErrorMatrixStatus::CrossTermsTruncated
+    } else {
+        ErrorMatrixStatus::Accurate
+    };
+    let gradient = FunctionGradient::new(grad, g2, gstep);
+    finish(fcn, state, trafo, hessian, gradient, hesse_failed, status)
+}
+
+/// Internal-space Hessian from the FCN's analytical `hessian()`, if any.
+///
+/// Used only when no variable parameter has limits, so internal and external
+/// space coincide. With limits the chain rule adds `grad_ext * d²ext/dint²`
+/// to the diagonal, which the external Hessian alone cannot supply; the
+/// finite-difference path handles those fits. Returns `None` when the FCN
+/// has no Hessian, it has the wrong size, or a variable parameter is limited.
+fn analytical_hessian(
+    fcn: &MnFcn,
+    trafo: &MnUserTransformation,
+    x: &DVector<f64>,
+) -> Option<DMatrix<f64>> {
+    if !AnalyticalGradientCalculator::can_compute_hessian(fcn.fcn()) {
+        return None;
+    }
+    let n = trafo.variable_parameters();
+    if (0..n).any(|i| has_any_limit(trafo, trafo.ext_of_int(i))) {
+        return None;
+    }
+    let external = trafo.transform(x.as_slice());
+    let packed = AnalyticalGradientCalculator::hessian(fcn.fcn(), &external)?;
+    let n_ext = trafo.parameters_len();
+    if packed.len() != n_ext * (n_ext + 1) / 2 {
+        return None;
+    }
+
+    let mut hessian = DMatrix::zeros(n, n);
+    for i in 0..n {
+        for j in i..n {
+            let (a, b) = (trafo.ext_of_int(i), trafo.ext_of_int(j));
+            let (r, c) = if a <= b { (a, b) } else { (b, a) };
+            let h = packed[r + c * (c + 1) / 2];
+            hessian[(i, j)] = h;
+            hessian[(j, i)] = h;
+        }
+    }
+    Some(hessian)
+}
+
+/// Internal-space diagonal second derivatives from the FCN's `g2()`, if any.
+///
+/// Only parameters without limits get an entry. For a limited parameter the
+/// chain rule adds `grad_ext * d²ext/dint²` to `g2_ext * dext²`; near a limit
+/// `dext` vanishes while the gradient does not, so those parameters are left
+/// to the finite-difference path. Returns `None` when the FCN has no `g2()`
+/// or it has the wrong size.
+fn analytical_g2(
+    fcn: &MnFcn,
+    trafo: &MnUserTransformation,
+    x: &DVector<f64>,
+) -> Option<Vec<Option<f64>>> {
+    if !fcn.fcn().has_g2() {
+        return None;
+    }
+    let external = trafo.transform(x.as_slice());
+    let g2 = AnalyticalGradientCalculator::g2(fcn.fcn(), &external)?;
+    if g2.len() != trafo.parameters_len() {
+        return None;
+    }
+    let n = trafo.variable_parameters();
+    Some(
+        (0..n)
+            .map(|i| {
+                let ext = trafo.ext_of_int(i);
+                (!has_any_limit(trafo, ext)).then(|| g2[ext])
+            })
+            .collect(),
+    )
+}
+
+/// Whether external parameter `ext` has a lower or upper limit.
+fn has_any_limit(trafo: &MnUserTransformation, ext: usize) -> bool {
+    let p = trafo.parameter(ext);
+    p.has_limits() || p.has_lower_limit() || p.has_upper_limit()
+}
+
+/// Steps 4-6: make the Hessian positive-definite, invert it and build the
+/// resulting state.
+fn finish(
+    fcn: &MnFcn,
+    state: &MinimumState,
+    trafo: &MnUserTransformation,
+    hessian: DMatrix<f64>,
+    gradient: FunctionGradient,
+    hesse_failed: bool,
+    status: ErrorMatrixStatus,
+) -> HesseResult {
+    let n = trafo.variable_parameters();
+    let eps2 = trafo.precision().eps2();
+
     // --- Step 4: Make positive-definite ---
     let (hessian_pd, was_modified) = make_pos_def(&hessian, trafo.precision());
 
@@ -219,7 +397,7 @@ pub fn calculate(
                 err.set_hesse_failed(true);
             }
             if !hesse_failed && !was_modified {
-                err.set_status(ErrorMatrixStatus::Accurate);
+                err.set_status(status);
             }
             (err, false)
         }
@@ -240,7 +418,6 @@ pub fn calculate(
     };
 
     // --- Step 6: EDM = 0.5 * g^T * V * g ---
-    let gradient = FunctionGradient::new(grad.clone(), g2, gstep);
     let edm = {
         let g = gradient.grad();
         let e = error.matrix();
@@ -260,5 +437,6 @@ pub fn calculate(
         hesse_failed,
         invert_failed,
         made_pos_def: was_modified,
+        ncalls: fcn.num_of_calls(),
     }
 }
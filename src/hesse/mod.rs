@@ -8,6 +8,10 @@
 pub mod calculator;
 pub mod gradient;
 
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use crate::application::default_max_fcn;
 use crate::fcn::FCN;
 use crate::global_cc::global_correlation_coefficients;
@@ -17,10 +21,41 @@ use crate::strategy::MnStrategy;
 use crate::user_covariance::MnUserCovariance;
 use crate::user_parameter_state::MnUserParameterState;
 
+/// Reason [`MnHesse::calculate_with_prior`] could not apply a prior.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriorError {
+    /// The prior is not `n×n` for `n` variable parameters.
+    DimensionMismatch { expected: usize, found: usize },
+    /// The prior covariance cannot be inverted.
+    SingularPrior,
+    /// Hesse gave no invertible covariance to combine with the prior.
+    SingularHesse,
+}
+
+impl fmt::Display for PriorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DimensionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "prior is {found}x{found}, expected {expected}x{expected}"
+                )
+            }
+            Self::SingularPrior => write!(f, "prior covariance is singular"),
+            Self::SingularHesse => write!(f, "Hesse covariance is singular"),
+        }
+    }
+}
+
+impl std::error::Error for PriorError {}
+
 /// Builder for running Hesse error analysis.
 pub struct MnHesse {
     strategy: MnStrategy,
     max_calls: Option<usize>,
+    max_cross_calls: Option<usize>,
+    diagonal_only: bool,
+    last_ncalls: AtomicUsize,
 }
 
 impl MnHesse {
@@ -29,6 +64,32 @@ impl MnHesse {
         Self {
             strategy: MnStrategy::default(),
             max_calls: None,
+            max_cross_calls: None,
+            diagonal_only: false,
+            last_ncalls: AtomicUsize::new(0),
+        }
+    }
+
+    /// Hesse with strategy 0 (fewest derivative cycles).
+    pub fn low() -> Self {
+        Self::new().with_strategy(0)
+    }
+
+    /// Hesse with strategy 1, the default.
+    pub fn medium() -> Self {
+        Self::new().with_strategy(1)
+    }
+
+    /// Hesse with strategy 2 (most accurate derivatives).
+    pub fn high() -> Self {
+        Self::new().with_strategy(2)
+    }
+
+    /// Hesse with the strategy chosen by [`MnStrategy::for_ndim`].
+    pub fn for_ndim(n: usize) -> Self {
+        Self {
+            strategy: MnStrategy::for_ndim(n),
+            ..Self::new()
         }
     }
 
@@ -44,6 +105,26 @@ impl MnHesse {
         self
     }
 
+    /// Limit the function calls spent on off-diagonal cross derivatives.
+    ///
+    /// A full Hessian needs n(n-1)/2 extra evaluations. Once `max` is used up,
+    /// the remaining off-diagonal elements are set to zero and the covariance
+    /// is flagged `ErrorMatrixStatus::CrossTermsTruncated`.
+    pub fn with_max_cross_calls(mut self, max: usize) -> Self {
+        self.max_cross_calls = Some(max);
+        self
+    }
+
+    /// Compute only the diagonal second derivatives.
+    ///
+    /// Skips the O(n²) cross-derivative evaluations, so the covariance is
+    /// diagonal and flagged `ErrorMatrixStatus::DiagonalOnly`. Exact for
+    /// separable functions; correlations are lost otherwise.
+    pub fn diagonal_only(mut self) -> Self {
+        self.diagonal_only = true;
+        self
+    }
+
     pub fn ncycles(&self) -> u32 {
         self.strategy.hessian_ncycles()
     }
@@ -56,6 +137,12 @@ impl MnHesse {
         self.strategy.hessian_g2_tolerance()
     }
 
+    /// Function evaluations used by the most recent `calculate` or
+    /// `calculate_errors` call (0 before the first).
+    pub fn last_ncalls(&self) -> usize {
+        self.last_ncalls.load(Ordering::Relaxed)
+    }
+
     /// Run Hesse on a minimization result.
     ///
     /// Returns a new FunctionMinimum with accurate covariance matrix.
@@ -67,7 +154,16 @@ impl MnHesse {
         let mn_fcn = MnFcn::new(fcn, trafo);
         let state = minimum.state();
 
-        let result = calculator::calculate(&mn_fcn, state, trafo, &self.strategy, maxcalls);
+        let result = calculator::calculate_with(
+            &mn_fcn,
+            state,
+            trafo,
+            &self.strategy,
+            maxcalls,
+            self.diagonal_only,
+            self.max_cross_calls,
+        );
+        self.last_ncalls.store(result.ncalls, Ordering::Relaxed);
 
         // Build new FunctionMinimum with the Hesse state
         let mut states = minimum.states().to_vec();
@@ -90,6 +186,68 @@ impl MnHesse {
         min
     }
 
+    /// Run Hesse and return `{name: (value, error)}` for the free parameters.
+    ///
+    /// Fixed and constant parameters are left out, so looking them up gives
+    /// `None`.
+    pub fn errors_as_map(
+        &self,
+        fcn: &dyn FCN,
+        minimum: &FunctionMinimum,
+    ) -> HashMap<String, (f64, f64)> {
+        let state = self.calculate_errors(fcn, minimum);
+        state
+            .params()
+            .params()
+            .iter()
+            .filter(|p| !p.is_fixed() && !p.is_const())
+            .map(|p| (p.name().to_string(), (p.value(), p.error())))
+            .collect()
+    }
+
+    /// Run Hesse with a Gaussian prior on the variable parameters.
+    ///
+    /// The user covariance becomes `(V^-1 + P^-1)^-1`, where `V` is the Hesse
+    /// covariance and `P` is `prior_covariance`, both in external space and
+    /// ordered like the variable parameters. Errors and global correlations
+    /// follow; the internal error matrix is the plain Hesse one.
+    ///
+    /// Returns [`PriorError::DimensionMismatch`] if the prior has the wrong
+    /// size, and [`PriorError::SingularPrior`] or
+    /// [`PriorError::SingularHesse`] if a matrix cannot be inverted.
+    pub fn calculate_with_prior(
+        &self,
+        fcn: &dyn FCN,
+        minimum: &FunctionMinimum,
+        prior_covariance: &MnUserCovariance,
+    ) -> Result<FunctionMinimum, PriorError> {
+        let mut result = self.calculate(fcn, minimum);
+        let n = result.seed().trafo().variable_parameters();
+        if prior_covariance.nrow() != n {
+            return Err(PriorError::DimensionMismatch {
+                expected: n,
+                found: prior_covariance.nrow(),
+            });
+        }
+        let h_prior = prior_covariance
+            .inverse()
+            .ok_or(PriorError::SingularPrior)?;
+        let h = result
+            .user_state()
+            .covariance()
+            .and_then(MnUserCovariance::inverse)
+            .ok_or(PriorError::SingularHesse)?;
+        let sum = h.data().iter().zip(h_prior.data()).map(|(a, b)| a + b);
+        let posterior = MnUserCovariance::from_vec(sum.collect(), n)
+            .inverse()
+            .ok_or(PriorError::SingularHesse)?;
+
+        let mut user_state = result.user_state().clone();
+        apply_user_covariance(&mut user_state, posterior, result.seed().trafo());
+        result.set_user_state(user_state);
+        Ok(result)
+    }
+
     /// Compute errors and covariance without modifying the FunctionMinimum.
     ///
     /// Returns an MnUserParameterState with updated errors and covariance.
@@ -105,7 +263,16 @@ impl MnHesse {
         let mn_fcn = MnFcn::new(fcn, trafo);
         let state = minimum.state();
 
-        let result = calculator::calculate(&mn_fcn, state, trafo, &self.strategy, maxcalls);
+        let result = calculator::calculate_with(
+            &mn_fcn,
+            state,
+            trafo,
+            &self.strategy,
+            maxcalls,
+            self.diagonal_only,
+            self.max_cross_calls,
+        );
+        self.last_ncalls.store(result.ncalls, Ordering::Relaxed);
 
         if !result.state.error().is_valid() {
             return minimum.user_state().clone();
@@ -156,6 +323,19 @@ fn build_user_state_with_covariance(
         }
     }
 
+    apply_user_covariance(&mut user_state, ext_cov, trafo);
+    user_state
+}
+
+/// Set errors, covariance and global correlations of `user_state` from the
+/// external covariance `ext_cov` over the variable parameters.
+fn apply_user_covariance(
+    user_state: &mut MnUserParameterState,
+    ext_cov: MnUserCovariance,
+    trafo: &crate::user_transformation::MnUserTransformation,
+) {
+    let n = ext_cov.nrow();
+
     // Parameter errors are sqrt(diagonal(user covariance)).
     for i in 0..n {
         let ext_i = trafo.ext_of_int(i);
@@ -163,9 +343,6 @@ fn build_user_state_with_covariance(
         user_state.set_error(ext_i, err);
     }
 
-    // Set covariance
-    user_state.set_covariance(ext_cov.clone());
-
     // Compute and set global correlation coefficients
     let mut cov_mat = nalgebra::DMatrix::zeros(n, n);
     for i in 0..n {
@@ -174,7 +351,6 @@ fn build_user_state_with_covariance(
         }
     }
     let (gcc, _) = global_correlation_coefficients(&cov_mat);
+    user_state.set_covariance(ext_cov);
     user_state.set_global_cc(gcc);
-
-    user_state
 }
@@ -59,6 +59,7 @@ pub mod precision;
 pub mod print;
 pub mod scan;
 pub mod simplex;
+pub mod soft_bound;
 pub mod strategy;
 pub mod transform;
 pub mod user_covariance;
@@ -71,18 +72,20 @@ pub mod python;
 
 // Re-exports for convenience
 pub use contours::MnContours;
-pub use fcn::{FCN, FCNGradient};
-pub use hesse::MnHesse;
+pub use fcn::{FCN, FCNGradient, FCNResiduals};
+pub use gradient::GradientMethod;
+pub use hesse::{MnHesse, PriorError};
 pub use migrad::MnMigrad;
 pub use minimize::MnMinimize;
-pub use minimum::FunctionMinimum;
+pub use minimum::{ErrorMatrixStatus, FunctionMinimum, MinimizationError};
 pub use minos::MnMinos;
-pub use parameter::MinuitParameter;
+pub use parameter::{MinuitParameter, ParameterError};
 pub use precision::MnMachinePrecision;
-pub use scan::{MnScan, MnScanMinimizer};
+pub use scan::{MnScan, MnScanMinimizer, ScanResult};
 pub use simplex::MnSimplex;
-pub use strategy::MnStrategy;
-pub use user_covariance::MnUserCovariance;
-pub use user_parameter_state::MnUserParameterState;
-pub use user_parameters::MnUserParameters;
+pub use soft_bound::SoftBoundedFCN;
+pub use strategy::{MnStrategy, StrategyValidationError};
+pub use user_covariance::{CovarianceError, MnUserCovariance};
+pub use user_parameter_state::{MnUserParameterState, UnpackError};
+pub use user_parameters::{DuplicateNameError, MnUserParameters};
 pub use user_transformation::MnUserTransformation;
@@ -3,13 +3,18 @@
 //! Main quasi-Newton iteration: compute step, line search, update gradient,
 //! and apply the DFP rank-2 inverse Hessian update.
 
+use std::cell::Cell;
+
 use nalgebra::DMatrix;
 
 #[cfg(feature = "trace")]
 use std::io::Write;
 
 use crate::fcn::FCNGradient;
-use crate::gradient::{AnalyticalGradientCalculator, Numerical2PGradientCalculator};
+use crate::gradient::{
+    AnalyticalGradientCalculator, GradientMethod, Numerical2PGradientCalculator,
+    Numerical4PGradientCalculator, Numerical5PGradientCalculator,
+};
 use crate::hesse::calculator as hesse_calculator;
 use crate::linesearch::mn_linesearch;
 use crate::minimum::error::{ErrorMatrixStatus, MinimumError};
@@ -23,6 +28,61 @@ use crate::strategy::MnStrategy;
 
 pub struct VariableMetricBuilder;
 
+/// User stop callback that remembers whether it ended the minimization.
+pub struct EarlyStop<'s> {
+    stop: &'s dyn Fn(&MinimumState) -> bool,
+    stopped: Cell<bool>,
+}
+
+impl<'s> EarlyStop<'s> {
+    pub fn new(stop: &'s dyn Fn(&MinimumState) -> bool) -> Self {
+        Self {
+            stop,
+            stopped: Cell::new(false),
+        }
+    }
+
+    /// Ask the callback about `state`, recording a request to stop.
+    pub fn check(&self, state: &MinimumState) -> bool {
+        let hit = (self.stop)(state);
+        if hit {
+            self.stopped.set(true);
+        }
+        hit
+    }
+
+    /// Whether the callback has asked to stop.
+    pub fn stopped(&self) -> bool {
+        self.stopped.get()
+    }
+}
+
+/// Migrad settings beyond the call budget and EDM target.
+///
+/// The default reproduces plain Minuit2 behaviour.
+#[derive(Clone, Copy, Default)]
+pub struct MigradOptions<'s> {
+    /// Cap on DFP iterations across all re-seeded passes, independent of the
+    /// call budget.
+    pub max_iterations: Option<usize>,
+    /// Scale each DFP correction by `1 - damping`, with `damping` in `[0, 1)`.
+    pub damping: f64,
+    /// Also require `‖grad‖_∞ < grad_tol` before accepting EDM convergence.
+    pub grad_tol: Option<f64>,
+    /// Finite-difference formula for numerical iteration gradients.
+    pub method: GradientMethod,
+    /// Checked on every new iteration state; a stop request ends the
+    /// minimization at that state.
+    pub stop: Option<&'s EarlyStop<'s>>,
+}
+
+impl MigradOptions<'_> {
+    /// Whether `stop` has ended the minimization.
+    pub fn stopped(&self) -> bool {
+        self.stop.is_some_and(EarlyStop::stopped)
+    }
+}
+
 #[cfg(feature = "trace")]
 fn trace_iteration(
     iter: usize,
@@ -76,12 +136,36 @@ impl VariableMetricBuilder {
         strategy: &MnStrategy,
         maxfcn: usize,
         edmval: f64,
+    ) -> Vec<MinimumState> {
+        Self::minimum_with_options(
+            fcn,
+            seed,
+            strategy,
+            maxfcn,
+            edmval,
+            &MigradOptions::default(),
+        )
+    }
+
+    /// [`VariableMetricBuilder::minimum`] with the extra settings in `options`.
+    pub fn minimum_with_options(
+        fcn: &MnFcn,
+        seed: &MinimumSeed,
+        strategy: &MnStrategy,
+        maxfcn: usize,
+        edmval: f64,
+        options: &MigradOptions,
     ) -> Vec<MinimumState> {
         let grad_calc = Numerical2PGradientCalculator::new(*strategy);
-        let next_grad = |p: &MinimumParameters, prev: &FunctionGradient| {
-            grad_calc.compute_with_previous(fcn, p, seed.trafo(), prev)
+        let grad_calc4 = Numerical4PGradientCalculator::new(*strategy);
+        let grad_calc5 = Numerical5PGradientCalculator::new(*strategy);
+        let trafo = seed.trafo();
+        let next_grad = |p: &MinimumParameters, prev: &FunctionGradient| match options.method {
+            GradientMethod::TwoPoint => grad_calc.compute_with_previous(fcn, p, trafo, prev),
+            GradientMethod::FourPoint => grad_calc4.compute(fcn, p, trafo, prev),
+            GradientMethod::FivePoint => grad_calc5.compute(fcn, p, trafo, prev),
         };
-        Self::minimize_with_reseed(fcn, seed, strategy, maxfcn, edmval, next_grad)
+        Self::minimize_with_reseed(fcn, seed, strategy, maxfcn, edmval, options, next_grad)
     }
 
     /// Top-level Migrad minimization with analytical gradients.
@@ -89,14 +173,36 @@ impl VariableMetricBuilder {
         fcn: &MnFcn,
         gradient_fcn: &dyn FCNGradient,
         seed: &MinimumSeed,
-        _strategy: &MnStrategy,
+        strategy: &MnStrategy,
         maxfcn: usize,
         edmval: f64,
+    ) -> Vec<MinimumState> {
+        Self::minimum_with_gradient_options(
+            fcn,
+            gradient_fcn,
+            seed,
+            strategy,
+            maxfcn,
+            edmval,
+            &MigradOptions::default(),
+        )
+    }
+
+    /// [`VariableMetricBuilder::minimum_with_gradient`] with the extra
+    /// settings in `options`; `options.method` is ignored.
+    pub fn minimum_with_gradient_options(
+        fcn: &MnFcn,
+        gradient_fcn: &dyn FCNGradient,
+        seed: &MinimumSeed,
+        strategy: &MnStrategy,
+        maxfcn: usize,
+        edmval: f64,
+        options: &MigradOptions,
     ) -> Vec<MinimumState> {
         let next_grad = |p: &MinimumParameters, _prev: &FunctionGradient| {
             AnalyticalGradientCalculator::compute(gradient_fcn, seed.trafo(), p)
         };
-        Self::minimize_with_reseed(fcn, seed, _strategy, maxfcn, edmval, next_grad)
+        Self::minimize_with_reseed(fcn, seed, strategy, maxfcn, edmval, options, next_grad)
     }
 
     /// Run variable-metric passes, re-seeding from the last state when ROOT's
@@ -111,9 +217,19 @@ impl VariableMetricBuilder {
         strategy: &MnStrategy,
         maxfcn: usize,
         edmval: f64,
+        options: &MigradOptions,
         mut next_grad: impl FnMut(&MinimumParameters, &FunctionGradient) -> FunctionGradient,
     ) -> Vec<MinimumState> {
-        let mut states = Self::iterate(fcn, seed, maxfcn, edmval, &mut next_grad);
+        let mut niter = 0usize;
+        let mut states = Self::iterate(
+            fcn,
+            seed,
+            maxfcn,
+            edmval,
+            options,
+            &mut niter,
+            &mut next_grad,
+        );
 
         // ROOT Minuit2 verifies a nominally converged variable-metric result
         // with MnHesse for strategy >= 2, and for strategy 1 when the updated
@@ -128,13 +244,19 @@ impl VariableMetricBuilder {
             strategy.strategy() >= 2 || (strategy.strategy() == 1 && state.error().dcovar() > 0.05)
         };
 
+        let gradient_converged =
+            |state: &MinimumState| Self::gradient_converged(state.gradient(), options.grad_tol);
+
         let maxfcn2 = (maxfcn as f64 * 1.3) as usize;
         for _pass in 0..5 {
+            if options.stopped() {
+                return states;
+            }
             let Some(last) = states.last() else {
                 return states;
             };
 
-            let mut must_continue = last.edm() >= edmval;
+            let mut must_continue = last.edm() >= edmval || !gradient_converged(last);
             if should_hesse(last) {
                 let mut hesse_strategy = *strategy;
                 hesse_strategy.set_hessian_force_pos_def(1);
@@ -144,8 +266,8 @@ impl VariableMetricBuilder {
                 let hesse_is_valid = hesse_state.is_valid() && hesse_state.error().is_valid();
                 let machine_limit = (seed.precision().eps2() * hesse_state.fval()).abs();
                 must_continue = hesse_is_valid
-                    && hesse_state.edm() > edmval
-                    && hesse_state.edm() >= machine_limit;
+                    && ((hesse_state.edm() > edmval && hesse_state.edm() >= machine_limit)
+                        || !gradient_converged(&hesse_state));
                 if !hesse_is_valid {
                     // ROOT keeps a failed Hesse result diagnostic, but the
                     // returned minimum is not advanced to an unusable Hesse
@@ -166,7 +288,7 @@ impl VariableMetricBuilder {
                 states.push(hesse_state);
             }
 
-            if !must_continue {
+            if !must_continue || options.max_iterations.is_some_and(|max| niter >= max) {
                 return states;
             }
 
@@ -187,7 +309,15 @@ impl VariableMetricBuilder {
                 seed.trafo().clone(),
             );
 
-            let states2 = Self::iterate(fcn, &seed2, maxfcn2, edmval, &mut next_grad);
+            let states2 = Self::iterate(
+                fcn,
+                &seed2,
+                maxfcn2,
+                edmval,
+                options,
+                &mut niter,
+                &mut next_grad,
+            );
             if states2.is_empty() {
                 return states;
             }
@@ -198,11 +328,16 @@ impl VariableMetricBuilder {
 
     /// Core quasi-Newton iteration: Newton step → pos-def fallback → line search
     /// → gradient update (via `next_grad`) → DFP update → EDM check.
+    ///
+    /// `niter` counts completed DFP iterations and is shared across re-seeded
+    /// passes so that `max_iterations` bounds the whole minimization.
     fn iterate(
         fcn: &MnFcn,
         seed: &MinimumSeed,
         maxfcn: usize,
         edmval: f64,
+        options: &MigradOptions,
+        niter: &mut usize,
         next_grad: &mut impl FnMut(&MinimumParameters, &FunctionGradient) -> FunctionGradient,
     ) -> Vec<MinimumState> {
         let n = seed.n_variable_params();
@@ -329,6 +464,7 @@ impl VariableMetricBuilder {
                 &params,
                 &new_gradient,
                 &gradient,
+                options.damping,
             );
 
             let mut new_error = MinimumError::new(v_updated, new_dcovar);
@@ -353,8 +489,17 @@ impl VariableMetricBuilder {
                 fcn.num_of_calls(),
             ));
 
+            *niter += 1;
+
+            if options
+                .stop
+                .is_some_and(|stop| stop.check(&states[states.len() - 1]))
+            {
+                break;
+            }
+
             let corrected_edm = edm * (1.0 + 3.0 * new_dcovar);
-            if corrected_edm < edmval {
+            if corrected_edm < edmval && Self::gradient_converged(&new_gradient, options.grad_tol) {
                 break;
             }
 
@@ -362,6 +507,10 @@ impl VariableMetricBuilder {
                 break;
             }
 
+            if options.max_iterations.is_some_and(|max| *niter >= max) {
+                break;
+            }
+
             iter += 1;
             params = new_params;
             error = new_error;
@@ -371,6 +520,11 @@ impl VariableMetricBuilder {
         states
     }
 
+    /// Whether the internal gradient satisfies the optional `‖grad‖_∞` bound.
+    fn gradient_converged(gradient: &FunctionGradient, grad_tol: Option<f64>) -> bool {
+        grad_tol.is_none_or(|tol| gradient.grad().amax() < tol)
+    }
+
     /// Rank-2 DFP update of the inverse Hessian approximation.
     ///
     /// Returns `(V_new, dcovar)` where `dcovar` measures how much the matrix changed.
@@ -381,7 +535,7 @@ impl VariableMetricBuilder {
         g_new: &FunctionGradient,
         g_old: &FunctionGradient,
     ) -> (DMatrix<f64>, f64) {
-        Self::dfp_update(error, p_new, p_old, g_new, g_old)
+        Self::dfp_update(error, p_new, p_old, g_new, g_old, 0.0)
     }
 
     pub fn nrow(error: &MinimumError) -> usize {
@@ -396,12 +550,14 @@ impl VariableMetricBuilder {
         states.push(state);
     }
 
+    /// `damping` scales the rank-2 correction: `V_new = V + (1 - damping) * V_upd`.
     fn dfp_update(
         error: &MinimumError,
         p_new: &MinimumParameters,
         p_old: &MinimumParameters,
         g_new: &FunctionGradient,
         g_old: &FunctionGradient,
+        damping: f64,
     ) -> (DMatrix<f64>, f64) {
         let v = error.matrix();
 
@@ -436,6 +592,9 @@ impl VariableMetricBuilder {
             // associativity exactly (f64 multiplication is not associative).
             v_upd += (&flnu * gvg) * flnu.transpose();
         }
+        if damping > 0.0 {
+            v_upd *= 1.0 - damping;
+        }
 
         let v_new = v + &v_upd;
 
@@ -3,10 +3,12 @@
 //! Orchestrates the Migrad minimization by generating the seed, then running
 //! the `VariableMetricBuilder` loop.
 
-use super::builder::VariableMetricBuilder;
+use super::builder::{MigradOptions, VariableMetricBuilder};
 use super::seed::MigradSeedGenerator;
 use crate::fcn::FCNGradient;
 use crate::minimum::FunctionMinimum;
+use crate::minimum::seed::MinimumSeed;
+use crate::minimum::state::MinimumState;
 use crate::mn_fcn::MnFcn;
 use crate::strategy::MnStrategy;
 use crate::user_transformation::MnUserTransformation;
@@ -14,13 +16,34 @@ use crate::user_transformation::MnUserTransformation;
 pub struct VariableMetricMinimizer;
 
 impl VariableMetricMinimizer {
-    /// Minimize using numerical gradients (central differences).
+    /// Minimize using numerical gradients.
     pub fn minimize(
         fcn: &MnFcn,
         trafo: &MnUserTransformation,
         strategy: &MnStrategy,
         maxfcn: usize,
         tolerance: f64,
+    ) -> FunctionMinimum {
+        Self::minimize_with_options(
+            fcn,
+            trafo,
+            strategy,
+            maxfcn,
+            tolerance,
+            &MigradOptions::default(),
+        )
+    }
+
+    /// Minimize using numerical gradients computed with `options.method`.
+    ///
+    /// The seed gradient always uses two-point central differences.
+    pub fn minimize_with_options(
+        fcn: &MnFcn,
+        trafo: &MnUserTransformation,
+        strategy: &MnStrategy,
+        maxfcn: usize,
+        tolerance: f64,
+        options: &MigradOptions,
     ) -> FunctionMinimum {
         let up = fcn.error_def();
 
@@ -35,28 +58,19 @@ impl VariableMetricMinimizer {
         let edmval = tolerance * up * 0.002;
 
         // Run variable-metric iteration
-        let states = VariableMetricBuilder::minimum(fcn, &seed, strategy, maxfcn, edmval);
-
-        // Check outcome. ROOT evaluates convergence after the Hesse-verified
-        // continuation with the extended budget before reporting a call limit
-        // (VariableMetricBuilder.cxx:177-198); a valid state converged inside
-        // (maxfcn, 1.3*maxfcn] must therefore not be marked call-limited.
-        let nfcn = fcn.num_of_calls();
-        if let Some(last) = states.last() {
-            if !last.error().is_valid() {
-                FunctionMinimum::above_max_edm(seed, states, up)
-            } else if last.edm() <= 10.0 * edmval {
-                FunctionMinimum::new(seed, states, up)
-            } else if nfcn >= maxfcn {
-                FunctionMinimum::with_call_limit(seed, states, up)
-            } else {
-                FunctionMinimum::above_max_edm(seed, states, up)
-            }
-        } else if nfcn >= maxfcn {
-            FunctionMinimum::with_call_limit(seed, states, up)
-        } else {
-            FunctionMinimum::new(seed, states, up)
-        }
+        let states = VariableMetricBuilder::minimum_with_options(
+            fcn, &seed, strategy, maxfcn, edmval, options,
+        );
+
+        Self::outcome(
+            seed,
+            states,
+            up,
+            edmval,
+            maxfcn,
+            fcn.num_of_calls(),
+            options.stopped(),
+        )
     }
 
     /// Minimize using analytical gradients provided by the user.
@@ -66,6 +80,26 @@ impl VariableMetricMinimizer {
         strategy: &MnStrategy,
         maxfcn: usize,
         tolerance: f64,
+    ) -> FunctionMinimum {
+        Self::minimize_with_gradient_options(
+            fcn,
+            trafo,
+            strategy,
+            maxfcn,
+            tolerance,
+            &MigradOptions::default(),
+        )
+    }
+
+    /// [`VariableMetricMinimizer::minimize_with_gradient`] with the extra
+    /// settings in `options`; `options.method` is ignored.
+    pub fn minimize_with_gradient_options(
+        fcn: &dyn FCNGradient,
+        trafo: &MnUserTransformation,
+        strategy: &MnStrategy,
+        maxfcn: usize,
+        tolerance: f64,
+        options: &MigradOptions,
     ) -> FunctionMinimum {
         let up = fcn.error_def();
 
@@ -83,14 +117,41 @@ impl VariableMetricMinimizer {
         let mn_fcn = MnFcn::new(fcn, trafo);
 
         // Run variable-metric iteration with analytical gradient calculator
-        let states = VariableMetricBuilder::minimum_with_gradient(
-            &mn_fcn, fcn, &seed, strategy, maxfcn, edmval,
+        let states = VariableMetricBuilder::minimum_with_gradient_options(
+            &mn_fcn, fcn, &seed, strategy, maxfcn, edmval, options,
         );
 
-        // Check outcome; see numerical-gradient path above for the ROOT
-        // continuation/call-limit ordering.
-        let nfcn = mn_fcn.num_of_calls();
-        if let Some(last) = states.last() {
+        Self::outcome(
+            seed,
+            states,
+            up,
+            edmval,
+            maxfcn,
+            mn_fcn.num_of_calls(),
+            options.stopped(),
+        )
+    }
+
+    /// Classify the iteration history as converged, call-limited or above
+    /// max EDM.
+    ///
+    /// ROOT evaluates convergence after the Hesse-verified continuation with
+    /// the extended budget before reporting a call limit
+    /// (VariableMetricBuilder.cxx:177-198); a valid state converged inside
+    /// (maxfcn, 1.3*maxfcn] must therefore not be marked call-limited. An
+    /// early stop is reported as a call limit.
+    fn outcome(
+        seed: MinimumSeed,
+        states: Vec<MinimumState>,
+        up: f64,
+        edmval: f64,
+        maxfcn: usize,
+        nfcn: usize,
+        stopped: bool,
+    ) -> FunctionMinimum {
+        if stopped {
+            FunctionMinimum::with_call_limit(seed, states, up)
+        } else if let Some(last) = states.last() {
             if !last.error().is_valid() {
                 FunctionMinimum::above_max_edm(seed, states, up)
             } else if last.edm() <= 10.0 * edmval {
@@ -8,19 +8,41 @@ pub mod builder;
 pub mod minimizer;
 pub mod seed;
 
+use std::collections::HashMap;
+
+use builder::{EarlyStop, MigradOptions};
+
 use crate::application::{DEFAULT_TOLERANCE, default_max_fcn};
 use crate::fcn::{FCN, FCNGradient};
-use crate::minimum::FunctionMinimum;
+use crate::gradient::GradientMethod;
+use crate::hesse::MnHesse;
+use crate::minimum::state::MinimumState;
+use crate::minimum::{FunctionMinimum, MinimizationError};
+use crate::minos::{MinosError, MnMinos};
 use crate::mn_fcn::MnFcn;
-use crate::strategy::MnStrategy;
+use crate::parameter::ParameterError;
+use crate::soft_bound::{SoftBound, SoftBoundedFCN};
+use crate::strategy::{MnStrategy, StrategyValidationError};
+use crate::user_parameter_state::MnUserParameterState;
 use crate::user_parameters::MnUserParameters;
 
 /// Builder for configuring and running Migrad minimization.
+#[derive(Clone)]
 pub struct MnMigrad {
     params: MnUserParameters,
     strategy: MnStrategy,
     max_fcn: Option<usize>,
-    tolerance: f64,
+    max_iterations: Option<usize>,
+    tolerance: Option<f64>,
+    tolerance_absolute: Option<f64>,
+    hessian_damping: f64,
+    gradient_tolerance: Option<f64>,
+    gradient_method: GradientMethod,
+    hesse_strategy: Option<u32>,
+    soft_bounds: Vec<SoftBound>,
+    /// `(value, error)` of each parameter as added, recorded before the
+    /// first warm start overwrites them.
+    initial_values: Vec<(f64, f64)>,
 }
 
 impl MnMigrad {
@@ -30,7 +52,15 @@ impl MnMigrad {
             params: MnUserParameters::new(),
             strategy: MnStrategy::default(),
             max_fcn: None,
-            tolerance: DEFAULT_TOLERANCE,
+            max_iterations: None,
+            tolerance: None,
+            tolerance_absolute: None,
+            hessian_damping: 0.0,
+            gradient_tolerance: None,
+            gradient_method: GradientMethod::TwoPoint,
+            hesse_strategy: None,
+            soft_bounds: Vec::new(),
+            initial_values: Vec::new(),
         }
     }
 
@@ -40,12 +70,104 @@ impl MnMigrad {
         self
     }
 
+    /// Use a hand-tuned strategy instead of a preset level.
+    ///
+    /// Returns every [`MnStrategy::validate`] violation if the strategy would
+    /// stall the derivative loops.
+    pub fn with_custom_strategy(
+        mut self,
+        strategy: MnStrategy,
+    ) -> Result<Self, Vec<StrategyValidationError>> {
+        strategy.validate()?;
+        self.strategy = strategy;
+        Ok(self)
+    }
+
+    /// Override the machine precision used for step sizes and tolerances.
+    pub fn with_precision(mut self, eps: f64) -> Self {
+        self.params.set_precision(eps);
+        self
+    }
+
     /// Add a free parameter.
     pub fn add(mut self, name: impl Into<String>, value: f64, error: f64) -> Self {
         self.params.add(name, value, error);
         self
     }
 
+    /// Add several free parameters from parallel slices.
+    ///
+    /// Returns [`ParameterError::LengthMismatch`] if the slices differ in length.
+    pub fn add_params(
+        mut self,
+        names: &[&str],
+        values: &[f64],
+        errors: &[f64],
+    ) -> Result<Self, ParameterError> {
+        if values.len() != names.len() || errors.len() != names.len() {
+            return Err(ParameterError::LengthMismatch);
+        }
+        for ((name, &value), &error) in names.iter().zip(values).zip(errors) {
+            self.params.add(*name, value, error);
+        }
+        Ok(self)
+    }
+
+    /// Add several doubly-bounded parameters from parallel slices.
+    ///
+    /// Returns [`ParameterError::LengthMismatch`] if the slices differ in length.
+    pub fn add_limited_params(
+        mut self,
+        names: &[&str],
+        values: &[f64],
+        errors: &[f64],
+        lowers: &[f64],
+        uppers: &[f64],
+    ) -> Result<Self, ParameterError> {
+        let n = names.len();
+        if [values.len(), errors.len(), lowers.len(), uppers.len()]
+            .iter()
+            .any(|&len| len != n)
+        {
+            return Err(ParameterError::LengthMismatch);
+        }
+        for i in 0..n {
+            self.params
+                .add_limited(names[i], values[i], errors[i], lowers[i], uppers[i]);
+        }
+        Ok(self)
+    }
+
+    /// Add free parameters named `prefix_0`, `prefix_1`, ...
+    ///
+    /// Returns [`ParameterError::LengthMismatch`] if the slices differ in length.
+    pub fn add_indexed(
+        self,
+        prefix: &str,
+        values: &[f64],
+        errors: &[f64],
+    ) -> Result<Self, ParameterError> {
+        let names = indexed_names(prefix, values.len());
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        self.add_params(&names, values, errors)
+    }
+
+    /// Add doubly-bounded parameters named `prefix_0`, `prefix_1`, ...
+    ///
+    /// Returns [`ParameterError::LengthMismatch`] if the slices differ in length.
+    pub fn add_indexed_limited(
+        self,
+        prefix: &str,
+        values: &[f64],
+        errors: &[f64],
+        lowers: &[f64],
+        uppers: &[f64],
+    ) -> Result<Self, ParameterError> {
+        let names = indexed_names(prefix, values.len());
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        self.add_limited_params(&names, values, errors, lowers, uppers)
+    }
+
     /// Add a parameter with both bounds.
     pub fn add_limited(
         mut self,
@@ -59,6 +181,32 @@ impl MnMigrad {
         self
     }
 
+    /// Add a free parameter kept within `[lower, upper]` by a quadratic penalty.
+    ///
+    /// The FCN is wrapped in a [`SoftBoundedFCN`] adding
+    /// `penalty_strength * max(0, lower - x)^2 + penalty_strength * max(0, x - upper)^2`.
+    /// Unlike [`MnMigrad::add_limited`] no transform is applied, so the
+    /// parameter can end slightly outside the bounds and the reported fval
+    /// includes any penalty.
+    pub fn add_soft_bounded(
+        mut self,
+        name: impl Into<String>,
+        value: f64,
+        error: f64,
+        lower: f64,
+        upper: f64,
+        penalty_strength: f64,
+    ) -> Self {
+        let par = self.params.add(name, value, error);
+        self.soft_bounds.push(SoftBound {
+            par,
+            lower,
+            upper,
+            strength: penalty_strength,
+        });
+        self
+    }
+
     /// Add a parameter with lower bound only.
     pub fn add_lower_limited(
         mut self,
@@ -95,51 +243,304 @@ impl MnMigrad {
         self
     }
 
+    /// Warm start: take values and errors from a previous fit.
+    ///
+    /// Parameters are matched by name; those not in `minimum` keep their
+    /// configured values. Limits and fixed flags are unchanged.
+    pub fn from_minimum(mut self, minimum: &FunctionMinimum) -> Self {
+        self.record_initial_values();
+        let state = minimum.user_state();
+        for ext in 0..self.params.len() {
+            let Some(i) = state.index(self.params.params()[ext].name()) else {
+                continue;
+            };
+            let p = state.parameter(i);
+            self.params.set_value(ext, p.value());
+            self.params.set_error(ext, p.error());
+        }
+        self
+    }
+
+    /// Restore every parameter's value and error to those given when it
+    /// was added, undoing [`MnMigrad::from_minimum`].
+    ///
+    /// Lets one configured builder be reused, e.g. for sensitivity studies.
+    pub fn reset_values(mut self) -> Self {
+        for (ext, &(value, error)) in self.initial_values.iter().enumerate() {
+            self.params.set_value(ext, value);
+            self.params.set_error(ext, error);
+        }
+        self
+    }
+
+    /// Record the as-added values of parameters not yet recorded.
+    fn record_initial_values(&mut self) {
+        let recorded = self.initial_values.len();
+        self.initial_values.extend(
+            self.params.params()[recorded..]
+                .iter()
+                .map(|p| (p.value(), p.error())),
+        );
+    }
+
     /// Set maximum number of function calls. Default = 200 + 100*n + 5*n^2.
     pub fn max_fcn(mut self, max: usize) -> Self {
         self.max_fcn = Some(max);
         self
     }
 
-    /// Set tolerance (relative to error_def). Default = 0.1.
+    /// Set a hard limit on the number of DFP iterations. Default = unlimited.
+    ///
+    /// Applied independently of `max_fcn`, so a function that is cheap in
+    /// calls but slow per iteration still stops after `n` iterations. A fit
+    /// stopped this way before reaching the EDM target is reported as above
+    /// max EDM.
+    pub fn max_iterations(mut self, n: usize) -> Self {
+        self.max_iterations = Some(n);
+        self
+    }
+
+    /// Set tolerance relative to Up (error_def). Default = 0.1.
+    ///
+    /// As in Minuit2's `SetTolerance`, Migrad stops once the EDM falls below
+    /// `0.002 * tol * Up`.
     pub fn tolerance(mut self, tol: f64) -> Self {
-        self.tolerance = tol;
+        self.tolerance = Some(tol);
+        self
+    }
+
+    /// Set the absolute EDM stopping threshold, independent of Up.
+    ///
+    /// When [`MnMigrad::tolerance`] is also set, the smaller of the two EDM
+    /// thresholds applies.
+    pub fn tolerance_absolute(mut self, tol: f64) -> Self {
+        self.tolerance_absolute = Some(tol);
+        self
+    }
+
+    /// Damp the DFP update of the inverse Hessian by `lambda` (default 0).
+    ///
+    /// Each rank-2 correction is scaled by `1 - lambda`, with `lambda`
+    /// clamped to `[0, 0.99]`, so the inverse Hessian adapts more slowly and
+    /// the fit usually needs more calls. Damping is not known to make
+    /// convergence more reliable; on Rosenbrock, badly scaled quadratics and
+    /// the Powell and Beale functions plain DFP does at least as well.
+    pub fn with_hessian_damping(mut self, lambda: f64) -> Self {
+        self.hessian_damping = lambda.clamp(0.0, 0.99);
+        self
+    }
+
+    /// Also require `‖grad‖_∞ < tol` before declaring convergence.
+    ///
+    /// Checked alongside the EDM criterion on the internal-parameter gradient.
+    /// EDM relies on the inverse-Hessian estimate, so when that is poorly
+    /// conditioned it can report convergence while the gradient is still
+    /// large; this bound prevents such early stops.
+    pub fn gradient_tolerance(mut self, tol: f64) -> Self {
+        self.gradient_tolerance = Some(tol);
+        self
+    }
+
+    /// Finite-difference formula for the numerical gradients (default
+    /// [`GradientMethod::TwoPoint`]).
+    ///
+    /// [`GradientMethod::FivePoint`] costs two more calls per parameter and step
+    /// cycle than two-point differences but is accurate to O(h⁴), which helps
+    /// when the gradient limits the final precision. Ignored by
+    /// [`MnMigrad::minimize_grad`].
+    pub fn gradient_method(mut self, method: GradientMethod) -> Self {
+        self.gradient_method = method;
+        self
+    }
+
+    /// Strategy level for the Hesse step of [`MnMigrad::minimize_with_minos`].
+    ///
+    /// Defaults to the Migrad strategy.
+    pub fn with_hesse_strategy(mut self, level: u32) -> Self {
+        self.hesse_strategy = Some(level);
         self
     }
 
     /// Run the minimization with numerical gradients (default).
     pub fn minimize(&self, fcn: &dyn FCN) -> FunctionMinimum {
+        self.minimize_until(fcn, &|_| false)
+    }
+
+    /// Run the minimization, ending it as soon as `stop` returns `true`.
+    ///
+    /// `stop` sees the state after every iteration. An early stop returns
+    /// that state with [`FunctionMinimum::reached_call_limit`] set, so it is
+    /// distinguishable from EDM convergence.
+    pub fn minimize_with_early_stop(
+        &self,
+        fcn: &dyn FCN,
+        stop: impl Fn(&MinimumState) -> bool,
+    ) -> FunctionMinimum {
+        self.minimize_until(fcn, &stop)
+    }
+
+    fn minimize_until(
+        &self,
+        fcn: &dyn FCN,
+        stop: &dyn Fn(&MinimumState) -> bool,
+    ) -> FunctionMinimum {
+        if !self.soft_bounds.is_empty() {
+            let bounded = SoftBoundedFCN::new(fcn, &self.soft_bounds);
+            return self.without_soft_bounds().minimize_until(&bounded, stop);
+        }
         let n = self.params.variable_parameters();
         let max_fcn = self.max_fcn.unwrap_or_else(|| default_max_fcn(n));
         let trafo = self.params.trafo().clone();
 
         let mn_fcn = MnFcn::new(fcn, &trafo);
-        minimizer::VariableMetricMinimizer::minimize(
+        let stop = EarlyStop::new(stop);
+        minimizer::VariableMetricMinimizer::minimize_with_options(
             &mn_fcn,
             &trafo,
             &self.strategy,
             max_fcn,
-            self.tolerance,
+            self.relative_tolerance(fcn.error_def()),
+            &self.options(Some(&stop)),
         )
     }
 
+    /// Run Migrad, then Hesse, then MINOS for each external index in `params`.
+    ///
+    /// Returns the Hesse-updated minimum and the MINOS errors keyed by
+    /// parameter index. Hesse uses the Migrad strategy unless
+    /// [`MnMigrad::with_hesse_strategy`] overrides it; MINOS uses the Migrad
+    /// strategy. Check [`FunctionMinimum::is_valid`] before trusting the
+    /// errors.
+    pub fn minimize_with_minos(
+        &self,
+        fcn: &dyn FCN,
+        params: &[usize],
+    ) -> (FunctionMinimum, HashMap<usize, MinosError>) {
+        if !self.soft_bounds.is_empty() {
+            let bounded = SoftBoundedFCN::new(fcn, &self.soft_bounds);
+            return self
+                .without_soft_bounds()
+                .minimize_with_minos(&bounded, params);
+        }
+        let level = self.strategy.strategy();
+        let minimum = self.minimize(fcn);
+        let minimum = MnHesse::new()
+            .with_strategy(self.hesse_strategy.unwrap_or(level))
+            .calculate(fcn, &minimum);
+        let errors = {
+            let minos = MnMinos::new(fcn, &minimum).with_strategy(level);
+            params
+                .iter()
+                .map(|&par| (par, minos.minos_error(par)))
+                .collect()
+        };
+        (minimum, errors)
+    }
+
+    /// Resume minimization from a saved parameter state.
+    ///
+    /// Replaces the configured parameters with `state.params()`, keeping
+    /// names, values, errors, limits and fixed flags, and runs
+    /// [`MnMigrad::minimize`] with the remaining settings. The state's
+    /// covariance is not used; the seed is rebuilt from the parameter errors.
+    ///
+    /// Soft bounds follow their parameter by name into the new layout;
+    /// returns [`ParameterError::UnknownName`] if `state` lacks a
+    /// soft-bounded parameter. Values recorded for
+    /// [`MnMigrad::reset_values`] are discarded.
+    pub fn minimize_from_state(
+        &self,
+        fcn: &dyn FCN,
+        state: MnUserParameterState,
+    ) -> Result<FunctionMinimum, ParameterError> {
+        let soft_bounds = self
+            .soft_bounds
+            .iter()
+            .map(|b| {
+                let name = self.params.params()[b.par].name();
+                state
+                    .index(name)
+                    .map(|par| SoftBound { par, ..*b })
+                    .ok_or_else(|| ParameterError::UnknownName(name.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            params: state.params().clone(),
+            soft_bounds,
+            initial_values: Vec::new(),
+            ..self.clone()
+        }
+        .minimize(fcn))
+    }
+
+    /// Run the minimization and reject invalid results.
+    ///
+    /// Returns [`MinimizationError::AboveMaxEdm`] when only the EDM criterion
+    /// failed, [`MinimizationError::CallLimit`] when the call limit was hit,
+    /// and [`MinimizationError::Invalid`] for any other invalid minimum.
+    pub fn minimize_strict(&self, fcn: &dyn FCN) -> Result<FunctionMinimum, MinimizationError> {
+        let min = self.minimize(fcn);
+        if min.is_valid() {
+            Ok(min)
+        } else if min.reached_call_limit() {
+            Err(MinimizationError::CallLimit { nfcn: min.nfcn() })
+        } else if min.is_above_max_edm() && min.state().is_valid() {
+            Err(MinimizationError::AboveMaxEdm { edm: min.edm() })
+        } else {
+            Err(MinimizationError::Invalid)
+        }
+    }
+
     /// Run the minimization with user-provided analytical gradients.
     ///
     /// Uses the analytical gradients provided by `FCNGradient::gradient()`.
     /// This typically requires fewer function evaluations than numerical differentiation.
     pub fn minimize_grad(&self, fcn: &dyn FCNGradient) -> FunctionMinimum {
+        if !self.soft_bounds.is_empty() {
+            let bounded = SoftBoundedFCN::new(fcn, &self.soft_bounds);
+            return self.without_soft_bounds().minimize_grad(&bounded);
+        }
         let n = self.params.variable_parameters();
         let max_fcn = self.max_fcn.unwrap_or_else(|| default_max_fcn(n));
         let trafo = self.params.trafo().clone();
 
-        minimizer::VariableMetricMinimizer::minimize_with_gradient(
+        minimizer::VariableMetricMinimizer::minimize_with_gradient_options(
             fcn,
             &trafo,
             &self.strategy,
             max_fcn,
-            self.tolerance,
+            self.relative_tolerance(fcn.error_def()),
+            &self.options(None),
         )
     }
+
+    /// Iteration settings for the variable-metric builder.
+    fn options<'s>(&self, stop: Option<&'s EarlyStop<'s>>) -> MigradOptions<'s> {
+        MigradOptions {
+            max_iterations: self.max_iterations,
+            damping: self.hessian_damping,
+            grad_tol: self.gradient_tolerance,
+            method: self.gradient_method,
+            stop,
+        }
+    }
+
+    /// Tolerance relative to `up` giving the stricter configured EDM threshold.
+    fn relative_tolerance(&self, up: f64) -> f64 {
+        let absolute = self.tolerance_absolute.map(|tol| tol / (0.002 * up));
+        match (self.tolerance, absolute) {
+            (Some(relative), Some(absolute)) => relative.min(absolute),
+            (Some(tol), None) | (None, Some(tol)) => tol,
+            (None, None) => DEFAULT_TOLERANCE,
+        }
+    }
+
+    fn without_soft_bounds(&self) -> Self {
+        Self {
+            soft_bounds: Vec::new(),
+            ..self.clone()
+        }
+    }
 }
 
 impl Default for MnMigrad {
@@ -147,3 +548,8 @@ impl Default for MnMigrad {
         Self::new()
     }
 }
+
+/// Names `prefix_0 .. prefix_{n-1}` for [`MnMigrad::add_indexed`].
+fn indexed_names(prefix: &str, n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("{prefix}_{i}")).collect()
+}
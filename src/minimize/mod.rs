@@ -15,13 +15,21 @@ use crate::parameter::MinuitParameter;
 use crate::simplex::MnSimplex;
 use crate::strategy::MnStrategy;
 use crate::user_parameters::MnUserParameters;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Default seed for [`MnMinimize::minimize_n_best`].
+const DEFAULT_SEED: u64 = 0x5EED_0FC0_FFEE;
 
 /// Builder for configuring and running hybrid Simplex+Migrad minimization.
+#[derive(Clone)]
 pub struct MnMinimize {
     params: MnUserParameters,
     strategy: MnStrategy,
     max_fcn: Option<usize>,
     tolerance: f64,
+    seed: u64,
+    simplex_first: bool,
 }
 
 impl MnMinimize {
@@ -32,6 +40,8 @@ impl MnMinimize {
             strategy: MnStrategy::default(),
             max_fcn: None,
             tolerance: DEFAULT_TOLERANCE,
+            seed: DEFAULT_SEED,
+            simplex_first: false,
         }
     }
 
@@ -108,6 +118,29 @@ impl MnMinimize {
         self
     }
 
+    /// Set the random seed used by [`MnMinimize::minimize_n_best`].
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Try Migrad before falling back to Simplex (default `true`).
+    ///
+    /// `false` is the same as `with_simplex_first(true)`.
+    pub fn with_migrad_first(mut self, migrad_first: bool) -> Self {
+        self.simplex_first = !migrad_first;
+        self
+    }
+
+    /// Always run Simplex, then refine with Migrad (default `false`).
+    ///
+    /// Preferred for multimodal or noisy functions, where Simplex is
+    /// expected to find a better basin than the first Migrad descent.
+    pub fn with_simplex_first(mut self, simplex_first: bool) -> Self {
+        self.simplex_first = simplex_first;
+        self
+    }
+
     fn configure_simplex_from_params(simplex: MnSimplex, params: &MnUserParameters) -> MnSimplex {
         configure_builder_from_params(simplex, params)
     }
@@ -123,10 +156,17 @@ impl MnMinimize {
     /// 2) If Migrad fails, run Simplex with strategy 2.
     /// 3) If Simplex succeeds, run Migrad again from that point (strategy 2).
     /// 4) If second Migrad fails, return the Simplex minimum.
+    ///
+    /// With [`MnMinimize::with_simplex_first`], steps 1-2 are skipped and
+    /// Simplex then Migrad run with the user-selected strategy.
     pub fn minimize(&self, fcn: &dyn FCN) -> FunctionMinimum {
         let n = self.params.variable_parameters();
         let max_fcn = self.max_fcn.unwrap_or_else(|| default_max_fcn(n));
 
+        if self.simplex_first {
+            return self.simplex_then_migrad(fcn, self.strategy.strategy(), max_fcn);
+        }
+
         // Attempt 1: Migrad with user-selected strategy.
         let migrad = Self::configure_migrad_from_params(
             MnMigrad::new().with_strategy(self.strategy.strategy()),
@@ -141,9 +181,16 @@ impl MnMinimize {
         }
 
         // Fallback path: use the more robust strategy level.
-        let fallback_strategy = 2_u32;
+        self.simplex_then_migrad(fcn, 2, max_fcn)
+    }
+
+    /// Simplex from the user parameters, then Migrad from the Simplex point.
+    ///
+    /// Returns the Simplex minimum if Simplex fails or the follow-up Migrad
+    /// is invalid.
+    fn simplex_then_migrad(&self, fcn: &dyn FCN, strategy: u32, max_fcn: usize) -> FunctionMinimum {
         let simplex = Self::configure_simplex_from_params(
-            MnSimplex::new().with_strategy(fallback_strategy),
+            MnSimplex::new().with_strategy(strategy),
             &self.params,
         )
         .max_fcn(max_fcn)
@@ -155,7 +202,7 @@ impl MnMinimize {
         }
 
         let migrad2 = Self::configure_migrad_from_params(
-            MnMigrad::new().with_strategy(fallback_strategy),
+            MnMigrad::new().with_strategy(strategy),
             simplex_min.user_state().params(),
         )
         .max_fcn(max_fcn)
@@ -164,6 +211,101 @@ impl MnMinimize {
 
         if min2.is_valid() { min2 } else { simplex_min }
     }
+
+    /// Multi-start search: minimize from `n_starts` perturbed starting points.
+    ///
+    /// Each free parameter is shifted by a Gaussian of width
+    /// `perturbation * error` (clamped to its limits) and the hybrid
+    /// minimizer runs from every start. Results are sorted by fval, best
+    /// first. The starts depend only on the seed set with
+    /// [`MnMinimize::with_seed`].
+    pub fn minimize_n_best(
+        &self,
+        fcn: &dyn FCN,
+        n_starts: usize,
+        perturbation: f64,
+    ) -> Vec<FunctionMinimum> {
+        let mut results: Vec<FunctionMinimum> = self
+            .perturbed_starts(n_starts, perturbation)
+            .into_iter()
+            .map(|start| start.minimize(fcn))
+            .collect();
+        results.sort_by(|a, b| a.fval().total_cmp(&b.fval()));
+        results
+    }
+
+    /// Parallel [`MnMinimize::minimize_n_best`] (requires `parallel` feature).
+    ///
+    /// Uses the same starting points, so results match the serial search.
+    #[cfg(feature = "parallel")]
+    pub fn minimize_n_best_parallel(
+        &self,
+        fcn: &(dyn FCN + Sync),
+        n_starts: usize,
+        perturbation: f64,
+    ) -> Vec<FunctionMinimum> {
+        let mut results: Vec<FunctionMinimum> = self
+            .perturbed_starts(n_starts, perturbation)
+            .into_par_iter()
+            .map(|start| start.minimize(fcn))
+            .collect();
+        results.sort_by(|a, b| a.fval().total_cmp(&b.fval()));
+        results
+    }
+
+    fn perturbed_starts(&self, n_starts: usize, perturbation: f64) -> Vec<MnMinimize> {
+        let mut rng = SplitMix64::new(self.seed);
+        (0..n_starts)
+            .map(|_| {
+                let mut start = self.clone();
+                for ext in 0..self.params.len() {
+                    let p = &self.params.params()[ext];
+                    if p.is_fixed() {
+                        continue;
+                    }
+                    let mut value = p.value() + perturbation * p.error() * rng.gaussian();
+                    if p.has_lower_limit() {
+                        value = value.max(p.lower_limit());
+                    }
+                    if p.has_upper_limit() {
+                        value = value.min(p.upper_limit());
+                    }
+                    start.params.set_value(ext, value);
+                }
+                start
+            })
+            .collect()
+    }
+}
+
+/// SplitMix64 generator with Box-Muller normals; enough for start points.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in (0, 1].
+    fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    fn gaussian(&mut self) -> f64 {
+        let u1 = self.uniform();
+        let u2 = self.uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
 }
 
 impl Default for MnMinimize {
@@ -6,7 +6,10 @@
 use nalgebra::DMatrix;
 
 /// How the error matrix was obtained.
+///
+/// Non-exhaustive: new ways of obtaining the matrix may be added.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ErrorMatrixStatus {
     /// Not calculated yet.
     NotAvailable,
@@ -16,6 +19,11 @@ pub enum ErrorMatrixStatus {
     MadePositiveDefinite,
     /// Full accurate calculation.
     Accurate,
+    /// Computed without off-diagonal terms (diagonal-only Hesse).
+    DiagonalOnly,
+    /// Some off-diagonal terms left at zero once the Hesse cross-derivative
+    /// budget ran out.
+    CrossTermsTruncated,
 }
 
 #[derive(Debug, Clone)]
@@ -9,13 +9,41 @@ pub mod parameters;
 pub mod seed;
 pub mod state;
 
+use std::collections::HashMap;
+use std::fmt;
+
+pub use error::ErrorMatrixStatus;
 use seed::MinimumSeed;
 use state::MinimumState;
 
+use crate::fcn::FCNResiduals;
 use crate::global_cc::global_correlation_coefficients;
 use crate::user_parameter_state::MnUserParameterState;
 use crate::user_parameters::MnUserParameters;
 
+/// Reason a minimization result was rejected by a strict minimizer call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MinimizationError {
+    /// Only the EDM criterion failed; carries the final EDM.
+    AboveMaxEdm { edm: f64 },
+    /// The function call limit was reached; carries the number of calls.
+    CallLimit { nfcn: usize },
+    /// The final state is invalid for another reason.
+    Invalid,
+}
+
+impl fmt::Display for MinimizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AboveMaxEdm { edm } => write!(f, "minimization above max EDM (edm = {edm})"),
+            Self::CallLimit { nfcn } => write!(f, "call limit reached after {nfcn} calls"),
+            Self::Invalid => write!(f, "minimization result is invalid"),
+        }
+    }
+}
+
+impl std::error::Error for MinimizationError {}
+
 /// Result of a minimization.
 #[derive(Debug, Clone)]
 pub struct FunctionMinimum {
@@ -68,6 +96,21 @@ impl FunctionMinimum {
         }
     }
 
+    /// Minimal result wrapping a user state produced outside this crate.
+    ///
+    /// The seed holds the state's values in internal space with a unit error
+    /// matrix and no iteration history; the user state is kept verbatim.
+    pub(crate) fn from_user_state(user_state: &MnUserParameterState, up: f64) -> Self {
+        let trafo = user_state.params().trafo().clone();
+        let internal = nalgebra::DVector::from_vec(trafo.initial_internal_values());
+        let params = parameters::MinimumParameters::new(internal, user_state.fval());
+        let state = MinimumState::from_params_edm(params, user_state.edm(), user_state.nfcn());
+
+        let mut min = Self::new(MinimumSeed::new(state, trafo), Vec::new(), up);
+        min.user_state = user_state.clone();
+        min
+    }
+
     fn build_user_state(seed: &MinimumSeed, last: &MinimumState, up: f64) -> MnUserParameterState {
         let trafo = seed.trafo();
         let internal = last.parameters().vec().as_slice();
@@ -193,6 +236,16 @@ impl FunctionMinimum {
         self.state().nfcn()
     }
 
+    /// Decrease of the function value from the seed to the final state.
+    pub fn fval_improvement(&self) -> f64 {
+        self.seed.fval() - self.fval()
+    }
+
+    /// Number of states recorded after the seed.
+    pub fn n_iterations(&self) -> usize {
+        self.states.len()
+    }
+
     /// Error definition (Up value): 1.0 for chi-square, 0.5 for likelihood.
     pub fn up(&self) -> f64 {
         self.up
@@ -207,6 +260,31 @@ impl FunctionMinimum {
         self.state().has_parameters()
     }
 
+    /// How the final error matrix was obtained.
+    pub fn error_matrix_status(&self) -> ErrorMatrixStatus {
+        self.state().error().status()
+    }
+
+    /// Whether the user state has a covariance with `Accurate` status.
+    ///
+    /// Guard for trusting error bars from the covariance matrix.
+    pub fn has_accurate_covariance(&self) -> bool {
+        self.user_state.has_covariance()
+            && self.error_matrix_status() == ErrorMatrixStatus::Accurate
+    }
+
+    /// Whether the user state has a covariance with `Accurate`,
+    /// `DiagonalOnly` or `CrossTermsTruncated` status.
+    pub fn has_approximate_covariance(&self) -> bool {
+        self.user_state.has_covariance()
+            && matches!(
+                self.error_matrix_status(),
+                ErrorMatrixStatus::Accurate
+                    | ErrorMatrixStatus::DiagonalOnly
+                    | ErrorMatrixStatus::CrossTermsTruncated
+            )
+    }
+
     pub fn has_made_pos_def_covar(&self) -> bool {
         self.state().error().is_made_pos_def()
     }
@@ -221,6 +299,87 @@ impl FunctionMinimum {
         self.reached_call_limit
     }
 
+    /// Stricter convergence test than [`FunctionMinimum::is_valid`].
+    ///
+    /// Requires a valid result, `edm < 1e-4 * up`, no free parameter within
+    /// one error of a limit, and an error matrix that did not have to be
+    /// forced positive-definite.
+    pub fn is_converged(&self) -> bool {
+        let error = self.state().error();
+        self.is_valid()
+            && self.edm() < 1e-4 * self.up
+            && !self.is_at_any_limit()
+            && error.is_valid()
+            && error.is_pos_def()
+    }
+
+    /// Stricter gate than [`FunctionMinimum::is_valid`] before MINOS or contours.
+    ///
+    /// Requires a valid result, `edm < 1e-5 * up`, an `Accurate` error matrix,
+    /// and a Hessian with condition number below `1e6`, i.e. no direction of
+    /// near-zero curvature.
+    pub fn is_global_minimum_candidate(&self) -> bool {
+        let error = self.state().error();
+        if !self.is_valid() || self.edm() >= 1e-5 * self.up || !error.is_accurate() {
+            return false;
+        }
+        // The covariance is the inverse Hessian, so their condition numbers agree.
+        let eigenvalues = error.matrix().clone().symmetric_eigenvalues();
+        let max = eigenvalues.amax();
+        let min = eigenvalues
+            .iter()
+            .fold(f64::INFINITY, |m, e| m.min(e.abs()));
+        min > 0.0 && max / min < 1e6
+    }
+
+    /// EDM relative to the Migrad stopping target for `tolerance`.
+    ///
+    /// `tolerance` is the value given to `MnMigrad::tolerance` (default 0.1);
+    /// the target is `0.002 * tolerance * up`, as in Migrad. Values below 1
+    /// mean converged, values near 1 mean marginal convergence.
+    pub fn convergence_score(&self, tolerance: f64) -> f64 {
+        self.edm() / (0.002 * tolerance * self.up)
+    }
+
+    /// Whether any free parameter is within `n_sigma` errors of a limit.
+    ///
+    /// See [`MinuitParameter::is_at_boundary`](crate::parameter::MinuitParameter::is_at_boundary).
+    pub fn any_at_boundary(&self, n_sigma: f64) -> bool {
+        self.user_state
+            .params()
+            .params()
+            .iter()
+            .any(|p| !p.is_fixed() && p.is_at_boundary(n_sigma))
+    }
+
+    /// Whether any free parameter is within one Hesse error of a limit.
+    ///
+    /// MINOS errors and the covariance of such parameters are unreliable; a
+    /// pipeline can widen the limits and minimize again.
+    pub fn is_at_any_limit(&self) -> bool {
+        self.any_at_boundary(1.0)
+    }
+
+    /// External indices of the free parameters within one error of a limit.
+    pub fn params_at_limit(&self) -> Vec<usize> {
+        self.user_state
+            .params()
+            .params()
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.is_fixed() && p.is_at_boundary(1.0))
+            .map(|(ext, _)| ext)
+            .collect()
+    }
+
+    /// Parameters for seeding a new minimization from this result.
+    ///
+    /// Values and errors are the fitted ones; names, limits and fixed flags
+    /// are carried over.
+    pub fn to_starting_params(&self) -> MnUserParameters {
+        self.user_state.params().clone()
+    }
+
     /// Parameter values in external (user) space.
     pub fn params(&self) -> Vec<f64> {
         self.seed
@@ -228,17 +387,110 @@ impl FunctionMinimum {
             .transform(self.state().parameters().vec().as_slice())
     }
 
+    /// `(value, error)` of every parameter, in external index order.
+    pub fn params_with_errors(&self) -> Vec<(f64, f64)> {
+        self.user_state
+            .params()
+            .params()
+            .iter()
+            .map(|p| (p.value(), p.error()))
+            .collect()
+    }
+
+    /// `(name, value, error)` of every parameter, in external index order.
+    pub fn named_params_with_errors(&self) -> Vec<(String, f64, f64)> {
+        self.user_state
+            .params()
+            .params()
+            .iter()
+            .map(|p| (p.name().to_string(), p.value(), p.error()))
+            .collect()
+    }
+
+    /// Global correlation coefficient of each variable parameter, by name.
+    ///
+    /// `None` when no covariance (and hence no global correlations) is
+    /// available.
+    pub fn global_cc_map(&self) -> Option<HashMap<String, f64>> {
+        let state = &self.user_state;
+        state.global_cc()?;
+        Some(
+            (0..state.len())
+                .filter_map(|ext| {
+                    let name = state.parameter(ext).name();
+                    state
+                        .global_cc_by_name(name)
+                        .map(|gcc| (name.to_string(), gcc))
+                })
+                .collect(),
+        )
+    }
+
+    /// Normalized residuals of a chi-square FCN at the fitted parameters.
+    pub fn compute_residuals(&self, fcn: &dyn FCNResiduals) -> Vec<f64> {
+        fcn.residuals(&self.params())
+    }
+
     /// Number of variable parameters.
     pub fn n_variable_params(&self) -> usize {
         self.seed.n_variable_params()
     }
 
+    /// Number of variable parameters that could actually move.
+    ///
+    /// Like [`FunctionMinimum::n_variable_params`], but also excludes
+    /// parameters whose lower and upper limits coincide.
+    pub fn n_active_parameters(&self) -> usize {
+        self.seed
+            .trafo()
+            .parameters()
+            .iter()
+            .filter(|p| {
+                let pinned = p.has_limits() && p.lower_limit() == p.upper_limit();
+                !(p.is_fixed() || p.is_const() || pinned)
+            })
+            .count()
+    }
+
+    /// Total number of parameters, including fixed and constant ones.
+    pub fn n_total_parameters(&self) -> usize {
+        self.user_state.len()
+    }
+
     pub fn set_error_def(&mut self, up: f64) {
         self.up = up;
         let rebuilt = Self::build_user_state(&self.seed, self.state(), up);
         self.user_state = rebuilt;
     }
 
+    /// Recompute the EDM of the final state as `0.5 * g^T V g`.
+    ///
+    /// Uses the stored gradient and inverse Hessian, and writes the result
+    /// back into the final state and the user state so EDM-based checks stay
+    /// consistent after `set_error_def` or `set_user_state`.
+    pub fn recompute_edm(&mut self) -> f64 {
+        let state = self.state();
+        let g = state.gradient().grad();
+        let edm = 0.5 * g.dot(&(state.error().matrix() * g));
+        match self.states.last_mut() {
+            Some(last) => last.set_edm(edm),
+            None => self.seed.state_mut().set_edm(edm),
+        }
+        self.user_state.set_edm(edm);
+        edm
+    }
+
+    /// Copy of `self` with the user state (values, errors, covariance) of
+    /// `hesse`.
+    ///
+    /// Seed, state history, EDM, nfcn and validity flags stay those of
+    /// `self`; use it to attach a Hesse covariance to the original fit.
+    pub fn update_from_hesse(&self, hesse: &FunctionMinimum) -> FunctionMinimum {
+        let mut merged = self.clone();
+        merged.user_state = hesse.user_state().clone();
+        merged
+    }
+
     /// Replace the user state (used by Hesse to inject covariance info).
     pub fn set_user_state(&mut self, state: MnUserParameterState) {
         self.user_state = state;
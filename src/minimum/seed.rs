@@ -26,6 +26,10 @@ impl MinimumSeed {
         &self.state
     }
 
+    pub(crate) fn state_mut(&mut self) -> &mut MinimumState {
+        &mut self.state
+    }
+
     pub fn trafo(&self) -> &MnUserTransformation {
         &self.trafo
     }
@@ -73,6 +73,10 @@ impl MinimumState {
         self.edm
     }
 
+    pub(crate) fn set_edm(&mut self, edm: f64) {
+        self.edm = edm;
+    }
+
     pub fn nfcn(&self) -> usize {
         self.nfcn
     }
@@ -22,6 +22,8 @@ pub struct MnCross {
     is_at_max_fcn: bool,
     /// Whether a new minimum was found during the search.
     new_minimum: bool,
+    /// Whether the crossing was located by the bisection fallback.
+    bisection: bool,
 }
 
 impl MnCross {
@@ -35,6 +37,15 @@ impl MnCross {
             is_at_limit: false,
             is_at_max_fcn: false,
             new_minimum: false,
+            bisection: false,
+        }
+    }
+
+    /// Successful crossing found by the bisection fallback.
+    pub fn valid_by_bisection(value: f64, state: MnUserParameterState, nfcn: usize) -> Self {
+        Self {
+            bisection: true,
+            ..Self::valid(value, state, nfcn)
         }
     }
 
@@ -48,6 +59,7 @@ impl MnCross {
             is_at_limit: true,
             is_at_max_fcn: false,
             new_minimum: false,
+            bisection: false,
         }
     }
 
@@ -61,6 +73,7 @@ impl MnCross {
             is_at_limit: false,
             is_at_max_fcn: true,
             new_minimum: false,
+            bisection: false,
         }
     }
 
@@ -74,6 +87,7 @@ impl MnCross {
             is_at_limit: false,
             is_at_max_fcn: false,
             new_minimum: true,
+            bisection: false,
         }
     }
 
@@ -87,6 +101,7 @@ impl MnCross {
             is_at_limit: false,
             is_at_max_fcn: false,
             new_minimum: false,
+            bisection: false,
         }
     }
 
@@ -121,4 +136,9 @@ impl MnCross {
     pub fn new_minimum(&self) -> bool {
         self.new_minimum
     }
+
+    /// Check if the parabolic search failed and bisection found the crossing.
+    pub fn converged_by_bisection(&self) -> bool {
+        self.bisection
+    }
 }
@@ -2,7 +2,8 @@
 //!
 //! Finds the point where the function value equals `fmin + Up`, by iteratively
 //! running Migrad with the scanned parameter fixed and using parabolic
-//! interpolation to converge on the crossing.
+//! interpolation to converge on the crossing. If the parabolic steps do not
+//! converge, a bisection on the bracketing points finishes the search.
 
 use crate::fcn::FCN;
 use crate::migrad::MnMigrad;
@@ -34,6 +35,23 @@ pub fn find_crossing(
     tlr: f64,
     maxcalls: usize,
     strategy: &MnStrategy,
+) -> MnCross {
+    find_crossing_traced(fcn, minimum, par, pmid, pdir, tlr, maxcalls, strategy, None)
+}
+
+/// [`find_crossing`] that also appends every intermediate Migrad result, in
+/// evaluation order, to `trace` when one is given.
+#[allow(clippy::too_many_arguments)]
+pub fn find_crossing_traced(
+    fcn: &dyn FCN,
+    minimum: &FunctionMinimum,
+    par: usize,
+    pmid: f64,
+    pdir: f64,
+    tlr: f64,
+    maxcalls: usize,
+    strategy: &MnStrategy,
+    mut trace: Option<&mut Vec<FunctionMinimum>>,
 ) -> MnCross {
     let up = minimum.up();
     let fmin = minimum.fval();
@@ -67,8 +85,16 @@ pub fn find_crossing(
         }
     }
 
+    let mut run_migrad = |val: f64| {
+        let result = run_migrad_fixed(fcn, minimum, par, val, &mgr_strategy, mgr_tlr, maxcalls);
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.push(result.clone());
+        }
+        result
+    };
+
     // --- Phase 2: First Migrad at pmid ---
-    let migrad_result = run_migrad_fixed(fcn, minimum, par, pmid, &mgr_strategy, mgr_tlr, maxcalls);
+    let migrad_result = run_migrad(pmid);
 
     let mut nfcn_total = migrad_result.nfcn();
     if !migrad_result.is_valid() {
@@ -99,7 +125,7 @@ pub fn find_crossing(
 
     // --- Phase 4: Second Migrad ---
     let p1 = pmid + aopt * pdir;
-    let migrad2 = run_migrad_fixed(fcn, minimum, par, p1, &mgr_strategy, mgr_tlr, maxcalls);
+    let migrad2 = run_migrad(p1);
     nfcn_total += migrad2.nfcn();
 
     if !migrad2.is_valid() {
@@ -138,7 +164,7 @@ pub fn find_crossing(
             return MnCross::limit_reached(nfcn_total);
         }
 
-        let mgr = run_migrad_fixed(fcn, minimum, par, p_try, &mgr_strategy, mgr_tlr, maxcalls);
+        let mgr = run_migrad(p_try);
         nfcn_total += mgr.nfcn();
 
         if !mgr.is_valid() {
@@ -164,7 +190,7 @@ pub fn find_crossing(
 
     // Evaluate
     let p_cross = pmid + a_cross * pdir;
-    let mgr_cross = run_migrad_fixed(fcn, minimum, par, p_cross, &mgr_strategy, mgr_tlr, maxcalls);
+    let mgr_cross = run_migrad(p_cross);
     nfcn_total += mgr_cross.nfcn();
 
     if !mgr_cross.is_valid() {
@@ -195,6 +221,9 @@ pub fn find_crossing(
     // We have 3 points: (a_left, f_left), (a_right, f_right), (a_cross, f_cross)
     let mut pts = Vec::from([(a_left, f_left), (a_right, f_right), (a_cross, f_cross)]);
     pts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    // Every evaluated point, kept for the bisection fallback: replacing the
+    // worst point below can drop one side of the bracket.
+    let mut history = pts.clone();
 
     let maxitr = 15;
     for _itr in 0..maxitr {
@@ -253,7 +282,7 @@ pub fn find_crossing(
             return MnCross::limit_reached(nfcn_total);
         }
 
-        let mgr = run_migrad_fixed(fcn, minimum, par, p_try, &mgr_strategy, mgr_tlr, maxcalls);
+        let mgr = run_migrad(p_try);
         nfcn_total += mgr.nfcn();
 
         if !mgr.is_valid() {
@@ -293,9 +322,51 @@ pub fn find_crossing(
             }
         }
         pts[worst_idx] = new_pt;
+        history.push(new_pt);
+    }
+
+    // --- Phase 8: Bisection fallback ---
+    // The parabolic steps did not converge; bisect any bracket of the target.
+    history.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let target = fmin + up;
+    let Some((mut lo, mut hi)) = history
+        .windows(2)
+        .map(|w| (w[0], w[1]))
+        .find(|(l, r)| (l.1 - target) * (r.1 - target) <= 0.0)
+    else {
+        return MnCross::invalid(nfcn_total);
+    };
+
+    let maxbisect = 20;
+    for _ in 0..maxbisect {
+        if nfcn_total >= maxcalls {
+            return MnCross::call_limit_reached(nfcn_total);
+        }
+
+        let a_mid = 0.5 * (lo.0 + hi.0);
+        let mgr = run_migrad(pmid + a_mid * pdir);
+        nfcn_total += mgr.nfcn();
+
+        if !mgr.is_valid() {
+            return MnCross::invalid(nfcn_total);
+        }
+        if is_new_minimum(mgr.fval(), fmin, fmin_delta) {
+            let state = mgr.user_state().clone();
+            return MnCross::new_minimum_found(state, nfcn_total);
+        }
+
+        let f_mid = mgr.fval();
+        if (f_mid - target).abs() < tlf {
+            let state = mgr.user_state().clone();
+            return MnCross::valid_by_bisection(a_mid, state, nfcn_total);
+        }
+        if (lo.1 - target) * (f_mid - target) <= 0.0 {
+            hi = (a_mid, f_mid);
+        } else {
+            lo = (a_mid, f_mid);
+        }
     }
 
-    // Didn't converge after maxitr — return best estimate
     MnCross::invalid(nfcn_total)
 }
 
@@ -308,6 +379,28 @@ fn run_migrad_fixed(
     strategy: &MnStrategy,
     tolerance: f64,
     maxcalls: usize,
+) -> FunctionMinimum {
+    run_migrad_with_fixed(
+        fcn,
+        minimum,
+        &[(fix_par, fix_val)],
+        strategy,
+        tolerance,
+        maxcalls,
+    )
+}
+
+/// Run Migrad from `minimum` with every `(index, value)` in `fixed` held fixed.
+///
+/// Parameters already fixed in the minimum stay fixed; all others are
+/// re-minimized. Shared by MINOS crossings and 2D contour crossings.
+pub(crate) fn run_migrad_with_fixed(
+    fcn: &dyn FCN,
+    minimum: &FunctionMinimum,
+    fixed: &[(usize, f64)],
+    strategy: &MnStrategy,
+    tolerance: f64,
+    maxcalls: usize,
 ) -> FunctionMinimum {
     let user_state = minimum.user_state();
     let nparams = user_state.len();
@@ -317,18 +410,21 @@ fn run_migrad_fixed(
         .tolerance(tolerance)
         .max_fcn(maxcalls);
 
-    // Add all parameters from the minimum, with the scan parameter fixed
+    // Add all parameters from the minimum, with the scan parameters fixed
     for i in 0..nparams {
         let p = user_state.parameter(i);
-        let val = if i == fix_par { fix_val } else { p.value() };
+        let scanned = fixed.iter().find(|&&(idx, _)| idx == i);
+        let val = scanned.map_or(p.value(), |&(_, v)| v);
         builder = add_parameter_to_builder(builder, p, val);
-        if i != fix_par && p.is_fixed() && !p.is_const() {
+        if scanned.is_none() && p.is_fixed() && !p.is_const() {
             builder = builder.fix(i);
         }
     }
 
-    // Fix the scan parameter.
-    builder = builder.fix(fix_par);
+    // Fix the scan parameters.
+    for &(idx, _) in fixed {
+        builder = builder.fix(idx);
+    }
 
     builder.minimize(fcn)
 }
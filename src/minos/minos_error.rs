@@ -3,6 +3,9 @@
 //! Wraps two crossing results (upper and lower) and computes the actual
 //! parameter error in external space.
 
+use std::collections::HashMap;
+use std::fmt;
+
 use super::cross::MnCross;
 
 /// Asymmetric MINOS errors for a single parameter.
@@ -94,6 +97,24 @@ impl MinosError {
         self.upper.at_limit()
     }
 
+    /// Whether the MINOS interval was cut off by a parameter limit.
+    ///
+    /// A truncated side is not a true `fmin + Up` crossing: the limit was hit
+    /// first, so the corresponding error does not have its usual coverage.
+    pub fn is_truncated_by_limit(&self) -> bool {
+        self.lower_truncated() || self.upper_truncated()
+    }
+
+    /// Whether the lower side was cut off by a parameter limit.
+    pub fn lower_truncated(&self) -> bool {
+        self.lower.at_limit()
+    }
+
+    /// Whether the upper side was cut off by a parameter limit.
+    pub fn upper_truncated(&self) -> bool {
+        self.upper.at_limit()
+    }
+
     pub fn at_lower_max_fcn(&self) -> bool {
         self.lower.at_max_fcn()
     }
@@ -118,4 +139,47 @@ impl MinosError {
     pub fn min(&self) -> f64 {
         self.min
     }
+
+    /// `(lower_error, upper_error, is_valid)`.
+    pub fn to_tuple(&self) -> (f64, f64, bool) {
+        (self.lower_error(), self.upper_error(), self.is_valid())
+    }
+
+    /// The errors keyed `"lower"`, `"upper"`, `"lower_valid"`, `"upper_valid"`
+    /// and `"value"`, for export to Python or JSON.
+    ///
+    /// The validity flags are stored as `1.0` or `0.0`.
+    pub fn to_dict(&self) -> HashMap<String, f64> {
+        let flag = |valid: bool| if valid { 1.0 } else { 0.0 };
+        HashMap::from([
+            ("lower".to_string(), self.lower_error()),
+            ("upper".to_string(), self.upper_error()),
+            ("lower_valid".to_string(), flag(self.lower_valid())),
+            ("upper_valid".to_string(), flag(self.upper_valid())),
+            ("value".to_string(), self.min),
+        ])
+    }
+}
+
+/// Failure of a name-based MINOS error lookup.
+///
+/// `MinosError` already names the per-parameter result type, so lookup
+/// failures use this separate enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MinosLookupError {
+    /// No parameter with this name exists in the minimum's user state.
+    ParameterNotFound(String),
+    /// The MINOS crossing search did not converge to a valid crossing.
+    CrossingNotFound,
 }
+
+impl fmt::Display for MinosLookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParameterNotFound(name) => write!(f, "parameter '{name}' not found"),
+            Self::CrossingNotFound => write!(f, "MINOS crossing not found"),
+        }
+    }
+}
+
+impl std::error::Error for MinosLookupError {}
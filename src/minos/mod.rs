@@ -8,20 +8,24 @@ pub mod function_cross;
 pub mod minos_error;
 
 pub use cross::MnCross;
-pub use minos_error::MinosError;
+pub use minos_error::{MinosError, MinosLookupError};
+
+use std::borrow::Cow;
 
 use crate::application::default_max_fcn;
 use crate::fcn::FCN;
 use crate::minimum::FunctionMinimum;
 use crate::strategy::MnStrategy;
+use crate::user_parameter_state::MnUserParameterState;
 
 /// Compute MINOS asymmetric errors.
 pub struct MnMinos<'a> {
     fcn: &'a dyn FCN,
-    minimum: &'a FunctionMinimum,
+    minimum: Cow<'a, FunctionMinimum>,
     strategy: MnStrategy,
     max_calls: Option<usize>,
     tolerance: f64,
+    lower_fraction: f64,
 }
 
 impl<'a> MnMinos<'a> {
@@ -29,10 +33,40 @@ impl<'a> MnMinos<'a> {
     pub fn new(fcn: &'a dyn FCN, minimum: &'a FunctionMinimum) -> Self {
         Self {
             fcn,
-            minimum,
+            minimum: Cow::Borrowed(minimum),
+            strategy: MnStrategy::default(),
+            max_calls: None,
+            tolerance: 0.1,
+            lower_fraction: 0.5,
+        }
+    }
+
+    /// Create a MINOS error calculator from a parameter state alone.
+    ///
+    /// For fits done by external code: the state's values and errors are
+    /// taken as the minimum and step sizes, with Up from `fcn.error_def()`.
+    pub fn from_user_state(fcn: &'a dyn FCN, user_state: &'a MnUserParameterState) -> Self {
+        let minimum = FunctionMinimum::from_user_state(user_state, fcn.error_def());
+        Self {
+            fcn,
+            minimum: Cow::Owned(minimum),
             strategy: MnStrategy::default(),
             max_calls: None,
             tolerance: 0.1,
+            lower_fraction: 0.5,
+        }
+    }
+
+    /// Create a MINOS error calculator for a thread-safe FCN (requires
+    /// `parallel` feature).
+    ///
+    /// The returned [`SyncMnMinos`] offers
+    /// [`SyncMnMinos::minos_error_parallel`] on top of the serial methods.
+    #[cfg(feature = "parallel")]
+    pub fn new_sync(fcn: &'a (dyn FCN + Sync), minimum: &'a FunctionMinimum) -> SyncMnMinos<'a> {
+        SyncMnMinos {
+            minos: Self::new(fcn, minimum),
+            fcn,
         }
     }
 
@@ -48,6 +82,17 @@ impl<'a> MnMinos<'a> {
         self
     }
 
+    /// Split the call budget of a parameter's two crossings (default 0.5).
+    ///
+    /// Together the crossings may use twice the per-crossing `max_calls`;
+    /// `lower_fraction` of that goes to the lower crossing and the rest to
+    /// the upper one. Clamped to `[0, 1]`. Give more to the harder side, e.g.
+    /// the lower one for a parameter close to its lower limit.
+    pub fn with_budget_split(mut self, lower_fraction: f64) -> Self {
+        self.lower_fraction = lower_fraction.clamp(0.0, 1.0);
+        self
+    }
+
     /// Set tolerance for crossing convergence (default 0.1).
     pub fn with_tolerance(mut self, tol: f64) -> Self {
         self.tolerance = tol;
@@ -75,14 +120,63 @@ impl<'a> MnMinos<'a> {
         MinosError::new(par, min_val, hesse_err, lo, up)
     }
 
+    /// [`MnMinos::minos_error`] together with every intermediate Migrad result.
+    ///
+    /// The fixed-parameter minimizations of the lower crossing search come
+    /// first, then those of the upper one, each in evaluation order. Useful to
+    /// see which values were probed when a crossing fails.
+    pub fn minos_with_intermediates(&self, par: usize) -> (MinosError, Vec<FunctionMinimum>) {
+        let search = self.search();
+        let mut trace = Vec::new();
+        let lo = search.find_crossing_traced(self.fcn, par, -1.0, Some(&mut trace));
+        let up = search.find_crossing_traced(self.fcn, par, 1.0, Some(&mut trace));
+        let p = self.minimum.user_state().parameter(par);
+        (MinosError::new(par, p.value(), p.error(), lo, up), trace)
+    }
+
+    /// Lower MINOS error for the parameter called `name`.
+    ///
+    /// Unlike [`MinosError::lower_error`], a failed crossing is reported as an
+    /// error instead of falling back to the Hesse error.
+    pub fn lower_error_by_name(&self, name: &str) -> Result<f64, MinosLookupError> {
+        let par = self.index_by_name(name)?;
+        let cross = self.lower(par);
+        if !cross.is_valid() {
+            return Err(MinosLookupError::CrossingNotFound);
+        }
+        let err = self.minimum.user_state().parameter(par).error();
+        Ok(-err * (1.0 + cross.value()))
+    }
+
+    /// Upper MINOS error for the parameter called `name`.
+    ///
+    /// Unlike [`MinosError::upper_error`], a failed crossing is reported as an
+    /// error instead of falling back to the Hesse error.
+    pub fn upper_error_by_name(&self, name: &str) -> Result<f64, MinosLookupError> {
+        let par = self.index_by_name(name)?;
+        let cross = self.upper(par);
+        if !cross.is_valid() {
+            return Err(MinosLookupError::CrossingNotFound);
+        }
+        let err = self.minimum.user_state().parameter(par).error();
+        Ok(err * (1.0 + cross.value()))
+    }
+
+    fn index_by_name(&self, name: &str) -> Result<usize, MinosLookupError> {
+        self.minimum
+            .user_state()
+            .index(name)
+            .ok_or_else(|| MinosLookupError::ParameterNotFound(name.to_string()))
+    }
+
     /// Lower crossing only.
     pub fn lower(&self, par: usize) -> MnCross {
-        self.find_crossing(par, -1.0)
+        self.search().find_crossing(self.fcn, par, -1.0)
     }
 
     /// Upper crossing only.
     pub fn upper(&self, par: usize) -> MnCross {
-        self.find_crossing(par, 1.0)
+        self.search().find_crossing(self.fcn, par, 1.0)
     }
 
     /// Alias for the lower crossing object.
@@ -114,7 +208,7 @@ impl<'a> MnMinos<'a> {
         let pmid = val + pdir;
         function_cross::find_crossing(
             self.fcn,
-            self.minimum,
+            &self.minimum,
             par,
             pmid,
             pdir,
@@ -124,9 +218,108 @@ impl<'a> MnMinos<'a> {
         )
     }
 
-    fn find_crossing(&self, par: usize, direction: f64) -> MnCross {
+    fn search(&self) -> CrossingSearch<'_> {
+        CrossingSearch {
+            minimum: &self.minimum,
+            strategy: self.strategy,
+            max_calls: self.max_calls,
+            tolerance: self.tolerance,
+            lower_fraction: self.lower_fraction,
+        }
+    }
+}
+
+/// [`MnMinos`] over a thread-safe FCN (requires `parallel` feature).
+///
+/// Built by [`MnMinos::new_sync`]; derefs to [`MnMinos`] for the serial
+/// methods.
+#[cfg(feature = "parallel")]
+pub struct SyncMnMinos<'a> {
+    minos: MnMinos<'a>,
+    fcn: &'a (dyn FCN + Sync),
+}
+
+#[cfg(feature = "parallel")]
+impl SyncMnMinos<'_> {
+    /// See [`MnMinos::with_strategy`].
+    pub fn with_strategy(mut self, level: u32) -> Self {
+        self.minos = self.minos.with_strategy(level);
+        self
+    }
+
+    /// See [`MnMinos::with_max_calls`].
+    pub fn with_max_calls(mut self, max: usize) -> Self {
+        self.minos = self.minos.with_max_calls(max);
+        self
+    }
+
+    /// See [`MnMinos::with_budget_split`].
+    pub fn with_budget_split(mut self, lower_fraction: f64) -> Self {
+        self.minos = self.minos.with_budget_split(lower_fraction);
+        self
+    }
+
+    /// See [`MnMinos::with_tolerance`].
+    pub fn with_tolerance(mut self, tol: f64) -> Self {
+        self.minos = self.minos.with_tolerance(tol);
+        self
+    }
+
+    /// [`MnMinos::minos_error`] with the lower and upper crossings searched
+    /// as independent rayon tasks.
+    ///
+    /// The two searches share no state, so results match the serial version.
+    pub fn minos_error_parallel(&self, par: usize) -> MinosError {
+        let search = self.minos.search();
+        let (lo, up) = rayon::join(
+            || search.find_crossing(self.fcn, par, -1.0),
+            || search.find_crossing(self.fcn, par, 1.0),
+        );
+        let p = self.minos.minimum.user_state().parameter(par);
+        MinosError::new(par, p.value(), p.error(), lo, up)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<'a> std::ops::Deref for SyncMnMinos<'a> {
+    type Target = MnMinos<'a>;
+
+    fn deref(&self) -> &MnMinos<'a> {
+        &self.minos
+    }
+}
+
+/// The settings one crossing search needs, without the FCN.
+///
+/// Kept apart from [`MnMinos`] so it can be shared across rayon tasks.
+struct CrossingSearch<'m> {
+    minimum: &'m FunctionMinimum,
+    strategy: MnStrategy,
+    max_calls: Option<usize>,
+    tolerance: f64,
+    lower_fraction: f64,
+}
+
+impl CrossingSearch<'_> {
+    fn find_crossing(&self, fcn: &dyn FCN, par: usize, direction: f64) -> MnCross {
+        self.find_crossing_traced(fcn, par, direction, None)
+    }
+
+    fn find_crossing_traced(
+        &self,
+        fcn: &dyn FCN,
+        par: usize,
+        direction: f64,
+        trace: Option<&mut Vec<FunctionMinimum>>,
+    ) -> MnCross {
         let nvar = self.minimum.n_variable_params();
         let maxcalls = self.max_calls.unwrap_or_else(|| default_cross_calls(nvar));
+        let fraction = if direction < 0.0 {
+            self.lower_fraction
+        } else {
+            1.0 - self.lower_fraction
+        };
+        let maxcalls = (2.0 * fraction * maxcalls as f64).round() as usize;
 
         let user_state = self.minimum.user_state();
         let p = user_state.parameter(par);
@@ -141,40 +334,18 @@ impl<'a> MnMinos<'a> {
         // The scan direction: parameter error scaled by direction
         let pdir = direction * err;
 
-        // Starting point: current value + step in direction
-        let pmid = val + pdir;
-
-        // Check limits
+        // Starting point: current value + step in direction, pulled back
+        // inside the limits if needed
+        let mut pmid = val + pdir;
         if direction > 0.0 && p.has_upper_limit() && pmid > p.upper_limit() {
-            let pmid = p.upper_limit() - 1e-6 * (p.upper_limit() - val).abs().max(1e-10);
-            return function_cross::find_crossing(
-                self.fcn,
-                self.minimum,
-                par,
-                pmid,
-                pdir,
-                self.tolerance,
-                maxcalls,
-                &self.strategy,
-            );
+            pmid = p.upper_limit() - 1e-6 * (p.upper_limit() - val).abs().max(1e-10);
         }
-
         if direction < 0.0 && p.has_lower_limit() && pmid < p.lower_limit() {
-            let pmid = p.lower_limit() + 1e-6 * (val - p.lower_limit()).abs().max(1e-10);
-            return function_cross::find_crossing(
-                self.fcn,
-                self.minimum,
-                par,
-                pmid,
-                pdir,
-                self.tolerance,
-                maxcalls,
-                &self.strategy,
-            );
+            pmid = p.lower_limit() + 1e-6 * (val - p.lower_limit()).abs().max(1e-10);
         }
 
-        function_cross::find_crossing(
-            self.fcn,
+        function_cross::find_crossing_traced(
+            fcn,
             self.minimum,
             par,
             pmid,
@@ -182,6 +353,7 @@ impl<'a> MnMinos<'a> {
             self.tolerance,
             maxcalls,
             &self.strategy,
+            trace,
         )
     }
 }
@@ -43,6 +43,11 @@ impl<'a> MnFcn<'a> {
         self.call_external(external)
     }
 
+    /// The wrapped user FCN.
+    pub fn fcn(&self) -> &'a dyn FCN {
+        self.fcn
+    }
+
     /// Get the total number of function calls made.
     pub fn num_of_calls(&self) -> usize {
         self.num_calls.get()
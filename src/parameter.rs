@@ -1,3 +1,25 @@
+use std::fmt;
+
+/// Invalid parameter definitions passed to a builder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParameterError {
+    /// Parallel slices describing parameters have different lengths.
+    LengthMismatch,
+    /// A parameter referenced by name is not defined.
+    UnknownName(String),
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch => write!(f, "parameter slices have different lengths"),
+            Self::UnknownName(name) => write!(f, "unknown parameter '{name}'"),
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
 /// A single minimization parameter with name, value, error, and optional bounds.
 ///
 /// Parameters can be free, fixed, or constant.
@@ -189,6 +211,16 @@ impl MinuitParameter {
         self.upper_limit
     }
 
+    /// Whether the value lies within `n_sigma` errors of a set limit.
+    ///
+    /// `n_sigma = 1.0` is the usual choice; MINOS errors of such parameters
+    /// are biased by the limit.
+    pub fn is_at_boundary(&self, n_sigma: f64) -> bool {
+        let margin = n_sigma * self.error;
+        (self.has_lower_limit && (self.value - self.lower_limit).abs() < margin)
+            || (self.has_upper_limit && (self.upper_limit - self.value).abs() < margin)
+    }
+
     // --- Fixed/Const ---
 
     pub fn fix(&mut self) {
@@ -234,6 +266,15 @@ mod tests {
         assert!((p.upper_limit() - 10.0).abs() < 1e-15);
     }
 
+    #[test]
+    fn boundary_detection() {
+        let p = MinuitParameter::with_limits(0, "x", 0.5, 1.0, 0.0, 10.0);
+        assert!(p.is_at_boundary(1.0));
+        assert!(!p.is_at_boundary(0.25));
+        let free = MinuitParameter::new(0, "y", 0.0, 1.0);
+        assert!(!free.is_at_boundary(100.0));
+    }
+
     #[test]
     fn fix_release() {
         let mut p = MinuitParameter::new(0, "x", 1.0, 0.1);
@@ -1,12 +1,11 @@
 use numpy::ndarray::Array2;
-use numpy::{IntoPyArray, PyArray1, PyArray2};
-use pyo3::exceptions::{
-    PyIndexError, PyKeyError, PyNotImplementedError, PyRuntimeError, PyTypeError, PyValueError,
-};
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray1};
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PyTuple};
 use std::collections::{HashMap, HashSet};
 
+use crate::contours::up_from_confidence_level;
 use crate::{FCN, FunctionMinimum, MnContours, MnHesse, MnMigrad, MnMinos, MnSimplex};
 
 // Aliases for the numpy array handles returned by the scan/profile/contour
@@ -350,6 +349,14 @@ fn resolve_param_name(names: &[String], key: &Bound<'_, PyAny>) -> PyResult<Stri
     }
 }
 
+/// Extract a 1-D float sequence, taking the zero-copy path for numpy arrays.
+fn extract_f64_vec(obj: &Bound<'_, PyAny>) -> PyResult<Vec<f64>> {
+    if let Ok(arr) = obj.extract::<PyReadonlyArray1<'_, f64>>() {
+        return Ok(arr.as_array().to_vec());
+    }
+    obj.extract::<Vec<f64>>()
+}
+
 fn list_iter(list: Bound<'_, PyList>) -> PyResult<Py<PyAny>> {
     Ok(list.into_any().try_iter()?.into_any().unbind())
 }
@@ -561,6 +568,8 @@ struct Minuit {
     init_fixed: HashSet<String>,
     init_limits: HashMap<String, (Option<f64>, Option<f64>)>,
     scan_fmin: Option<FMin>,
+    /// Calls already counted when the context manager last exited.
+    nfcn_offset: usize,
 }
 
 #[pymethods]
@@ -571,14 +580,26 @@ impl Minuit {
     const LIKELIHOOD: f64 = 0.5;
 
     #[new]
-    #[pyo3(signature = (fcn, *args, name=None, **kwds))]
+    #[pyo3(signature = (fcn, *args, name=None, values=None, **kwds))]
     fn new(
         py: Python<'_>,
         fcn: Py<PyAny>,
         args: Vec<f64>,
         name: Option<Vec<String>>,
+        values: Option<Bound<'_, PyAny>>,
         kwds: Option<Bound<'_, PyDict>>,
     ) -> PyResult<Self> {
+        // `values=` accepts a numpy array or any float sequence and is
+        // equivalent to passing the same starting values positionally.
+        let args = match values {
+            Some(_) if !args.is_empty() => {
+                return Err(PyValueError::new_err(
+                    "pass starting values either positionally or via values=, not both",
+                ));
+            }
+            Some(v) => extract_f64_vec(&v)?,
+            None => args,
+        };
         let names = if let Some(name) = name {
             name
         } else {
@@ -602,7 +623,13 @@ impl Minuit {
                 let mut fallback = Vec::new();
                 if let Some(p) = &kwds {
                     for (key, _) in p.iter() {
-                        fallback.push(key.extract::<String>()?);
+                        let key = key.extract::<String>()?;
+                        let is_error = key
+                            .strip_prefix("error_")
+                            .is_some_and(|par| p.contains(par).unwrap_or(false));
+                        if !is_error {
+                            fallback.push(key);
+                        }
                     }
                 }
                 fallback
@@ -617,7 +644,6 @@ impl Minuit {
 
         for name in &names {
             values.insert(name.clone(), 0.0);
-            errors.insert(name.clone(), 0.1);
         }
 
         if args.len() > names.len() {
@@ -630,17 +656,38 @@ impl Minuit {
         if let Some(p) = kwds {
             for (name, value) in p.iter() {
                 let name_str = name.extract::<String>()?;
-                if !values.contains_key(&name_str) {
+                let val = value.extract::<f64>()?;
+                if let Some(v) = values.get_mut(&name_str) {
+                    *v = val;
+                } else if let Some(par) = name_str.strip_prefix("error_")
+                    && values.contains_key(par)
+                {
+                    errors.insert(par.to_string(), val);
+                } else {
                     return Err(PyValueError::new_err(format!(
                         "unknown parameter: {}",
                         name_str
                     )));
                 }
-                let val = value.extract::<f64>()?;
-                values.insert(name_str, val);
             }
         }
 
+        // Parameters without an explicit `error_<name>` get a step scaled
+        // to their starting value.
+        for name in &names {
+            errors
+                .entry(name.clone())
+                .or_insert_with(|| (0.1 * values[name].abs()).max(1e-3));
+        }
+
+        // iminuit convention: a cost function may carry its own `errordef`
+        // (1.0 for least squares, 0.5 for negative log-likelihood).
+        let errordef = fcn
+            .bind(py)
+            .getattr("errordef")
+            .and_then(|e| e.extract::<f64>())
+            .unwrap_or(1.0);
+
         let init_values = values.clone();
         let init_errors = errors.clone();
         let init_fixed = fixed.clone();
@@ -657,16 +704,41 @@ impl Minuit {
             strategy: 1,
             tolerance: 0.1,
             max_calls: None,
-            errordef: 1.0,
+            errordef,
             merrors: HashMap::new(),
             init_values,
             init_errors,
             init_fixed,
             init_limits,
             scan_fmin: None,
+            nfcn_offset: 0,
         })
     }
 
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Print the fit summary if the last fit failed and reset the call
+    /// counter. Exceptions raised inside the block are not suppressed.
+    #[pyo3(signature = (_exc_type=None, _exc_value=None, _traceback=None))]
+    fn __exit__(
+        &mut self,
+        py: Python<'_>,
+        _exc_type: Option<Bound<'_, PyAny>>,
+        _exc_value: Option<Bound<'_, PyAny>>,
+        _traceback: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        if let Some(min) = &self.last_minimum
+            && !min.is_valid()
+        {
+            py.import("builtins")?
+                .call_method1("print", (format!("Fit failed:\n{min}"),))?;
+        }
+        self.nfcn_offset += self.get_nfcn();
+        Ok(false)
+    }
+
     #[getter]
     fn get_errordef(&self) -> f64 {
         self.errordef
@@ -683,11 +755,22 @@ impl Minuit {
     }
 
     #[setter]
-    fn set_strategy(&mut self, v: u32) -> PyResult<()> {
-        if v > 2 {
-            return Err(PyValueError::new_err("strategy must be 0, 1, or 2"));
-        }
-        self.strategy = v;
+    fn set_strategy(&mut self, v: Bound<'_, PyAny>) -> PyResult<()> {
+        let level = if let Ok(level) = v.extract::<u32>() {
+            Some(level).filter(|l| *l <= 2)
+        } else if let Ok(name) = v.extract::<String>() {
+            match name.as_str() {
+                "low" => Some(0),
+                "medium" => Some(1),
+                "high" => Some(2),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        self.strategy = level.ok_or_else(|| {
+            PyValueError::new_err("strategy must be 0, 1, 2, \"low\", \"medium\" or \"high\"")
+        })?;
         Ok(())
     }
 
@@ -717,6 +800,32 @@ impl Minuit {
         }
     }
 
+    /// Current parameter values as a numpy array, in parameter order.
+    fn get_values_array<'py>(&self, py: Python<'py>) -> Arr1f<'py> {
+        let vals: Vec<f64> = self
+            .names
+            .iter()
+            .map(|n| *self.values.get(n).unwrap_or(&0.0))
+            .collect();
+        PyArray1::from_vec(py, vals)
+    }
+
+    /// Set all parameter values from a numpy array, in parameter order.
+    fn set_values_array(&mut self, arr: PyReadonlyArray1<'_, f64>) -> PyResult<()> {
+        let arr = arr.as_array();
+        if arr.len() != self.names.len() {
+            return Err(PyValueError::new_err(format!(
+                "expected {} values, got {}",
+                self.names.len(),
+                arr.len()
+            )));
+        }
+        for (name, &v) in self.names.iter().zip(arr.iter()) {
+            self.values.insert(name.clone(), v);
+        }
+        Ok(())
+    }
+
     #[getter]
     fn get_errors(slf: Bound<'_, Self>) -> ErrorView {
         ErrorView {
@@ -797,24 +906,37 @@ impl Minuit {
             .or_else(|| self.last_minimum.as_ref().map(|m| m.is_valid()))
     }
 
+    /// Full covariance over all parameters; rows and columns of fixed
+    /// parameters are zero, as in iminuit.
     #[getter]
     fn get_covariance(&self) -> Option<Vec<Vec<f64>>> {
-        if self.scan_fmin.is_none()
-            && let Some(min) = &self.last_minimum
-            && let Some(cov) = min.user_state().covariance()
-        {
-            let n = cov.nrow();
-            let mut matrix = Vec::with_capacity(n);
-            for r in 0..n {
-                let mut row = Vec::with_capacity(n);
-                for c in 0..n {
-                    row.push(cov.get(r, c));
-                }
-                matrix.push(row);
-            }
-            return Some(matrix);
-        }
-        None
+        let state = self.fitted_state()?;
+        let cov = state.covariance()?;
+        let n = state.len();
+        let int_of_ext: Vec<Option<usize>> = (0..n).map(|e| state.int_of_ext(e)).collect();
+        let matrix = (0..n)
+            .map(|r| {
+                (0..n)
+                    .map(|c| match (int_of_ext[r], int_of_ext[c]) {
+                        (Some(ir), Some(ic)) => cov.get(ir, ic),
+                        _ => 0.0,
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(matrix)
+    }
+
+    /// Covariance restricted to the variable parameters.
+    #[getter]
+    fn get_covariance_variable(&self) -> Option<Vec<Vec<f64>>> {
+        let cov = self.fitted_state()?.covariance()?;
+        let n = cov.nrow();
+        Some(
+            (0..n)
+                .map(|r| (0..n).map(|c| cov.get(r, c)).collect())
+                .collect(),
+        )
     }
 
     #[getter]
@@ -858,6 +980,52 @@ impl Minuit {
             .collect()
     }
 
+    /// Parameter summary as a dict of columns, ready for `pandas.DataFrame`.
+    fn params_df(&self, py: Python<'_>) -> PyResult<Py<PyDict>> {
+        let params = self.get_params();
+        let dict = PyDict::new(py);
+        dict.set_item(
+            "name",
+            params.iter().map(|p| p.name.clone()).collect::<Vec<_>>(),
+        )?;
+        dict.set_item("value", params.iter().map(|p| p.value).collect::<Vec<_>>())?;
+        dict.set_item("error", params.iter().map(|p| p.error).collect::<Vec<_>>())?;
+        dict.set_item(
+            "lower",
+            params.iter().map(|p| p.lower_limit).collect::<Vec<_>>(),
+        )?;
+        dict.set_item(
+            "upper",
+            params.iter().map(|p| p.upper_limit).collect::<Vec<_>>(),
+        )?;
+        dict.set_item(
+            "fixed",
+            params.iter().map(|p| p.is_fixed).collect::<Vec<_>>(),
+        )?;
+        Ok(dict.into())
+    }
+
+    /// Correlation matrix over all parameters, or `None` without a
+    /// covariance. Rows and columns of fixed parameters are zero.
+    fn matrix(&self) -> Option<Vec<Vec<f64>>> {
+        let cov = self.get_covariance()?;
+        let sigma: Vec<f64> = (0..cov.len()).map(|i| cov[i][i].sqrt()).collect();
+        Some(
+            cov.iter()
+                .enumerate()
+                .map(|(r, row)| {
+                    row.iter()
+                        .enumerate()
+                        .map(|(c, v)| {
+                            let norm = sigma[r] * sigma[c];
+                            if norm > 0.0 { v / norm } else { 0.0 }
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
     #[getter]
     fn get_merrors(&self, py: Python<'_>) -> PyResult<Py<PyDict>> {
         let dict = PyDict::new(py);
@@ -874,6 +1042,7 @@ impl Minuit {
             .map(|m| m.nfcn)
             .or_else(|| self.last_minimum.as_ref().map(|m| m.nfcn()))
             .unwrap_or(0)
+            .saturating_sub(self.nfcn_offset)
     }
 
     #[getter]
@@ -908,6 +1077,7 @@ impl Minuit {
         slf.limits = slf.init_limits.clone();
         slf.last_minimum = None;
         slf.scan_fmin = None;
+        slf.nfcn_offset = 0;
         slf.merrors.clear();
         slf.into()
     }
@@ -945,6 +1115,7 @@ impl Minuit {
             };
             slf.merrors.clear();
             slf.scan_fmin = None;
+            slf.nfcn_offset = 0;
             let minimizer = slf.build_migrad();
             let result = minimizer.minimize(&fcn);
             slf.update_state_from_result(&result);
@@ -961,6 +1132,7 @@ impl Minuit {
             };
             slf.merrors.clear();
             slf.scan_fmin = None;
+            slf.nfcn_offset = 0;
             let minimizer = slf.build_simplex();
             let result = minimizer.minimize(&fcn);
             slf.update_state_from_result(&result);
@@ -1124,14 +1296,20 @@ impl Minuit {
         Ok((PyArray1::from_vec(py, xs), PyArray1::from_vec(py, fs)))
     }
 
-    #[pyo3(signature = (vname, *, size=30, bound=2.0, subtract_min=false))]
+    /// Profile likelihood: re-minimize all other parameters at each point.
+    ///
+    /// `bound` is either a number of standard errors around the current value
+    /// or an explicit `(low, high)` range. `bins` is accepted as the iminuit 1.x
+    /// spelling of `size`.
+    #[pyo3(signature = (vname, *, size=30, bound=None, subtract_min=false, bins=None))]
     fn mnprofile<'py>(
         &self,
         py: Python<'py>,
         vname: String,
         size: usize,
-        bound: f64,
+        bound: Option<Bound<'py, PyAny>>,
         subtract_min: bool,
+        bins: Option<usize>,
     ) -> PyResult<(Arr1f<'py>, Arr1f<'py>, Arr1b<'py>)> {
         let _min = self
             .last_minimum
@@ -1141,6 +1319,7 @@ impl Minuit {
             .iter()
             .position(|n| *n == vname)
             .ok_or_else(|| PyValueError::new_err(format!("Parameter '{}' not found", vname)))?;
+        let size = bins.unwrap_or(size);
         let v = *self.values.get(&vname).unwrap_or(&0.0);
         let e = self
             .errors
@@ -1149,8 +1328,18 @@ impl Minuit {
             .unwrap_or(0.1)
             .abs()
             .max(1e-10);
-        let low = v - bound * e;
-        let high = v + bound * e;
+        let (low, high) = match bound {
+            None => (v - 2.0 * e, v + 2.0 * e),
+            Some(b) => {
+                if let Ok(nsigma) = b.extract::<f64>() {
+                    (v - nsigma * e, v + nsigma * e)
+                } else {
+                    b.extract::<(f64, f64)>().map_err(|_| {
+                        PyTypeError::new_err("bound must be a number or a (low, high) tuple")
+                    })?
+                }
+            }
+        };
         let step = if size > 1 {
             (high - low) / (size - 1) as f64
         } else {
@@ -1183,24 +1372,29 @@ impl Minuit {
         ))
     }
 
-    #[pyo3(signature = (x, y, *, cl=None, size=100))]
+    #[pyo3(signature = (x, y, *, cl=None, size=None, numpoints=None))]
     fn mncontour<'py>(
         &self,
         py: Python<'py>,
         x: String,
         y: String,
         cl: Option<f64>,
-        size: usize,
+        size: Option<usize>,
+        numpoints: Option<usize>,
     ) -> PyResult<Arr2f<'py>> {
-        if cl.is_some() {
-            return Err(PyNotImplementedError::new_err(
-                "cl scaling not yet implemented; mncontour uses the errordef-defined level",
-            ));
+        let cl = cl.unwrap_or(0.68);
+        if !(cl > 0.0 && cl < 1.0) {
+            return Err(PyValueError::new_err(format!(
+                "cl must be in (0, 1), got {cl}"
+            )));
         }
         let min = self
             .last_minimum
             .as_ref()
             .ok_or_else(|| PyRuntimeError::new_err("Run migrad/simplex first"))?;
+        let mut min = min.clone();
+        min.set_error_def(up_from_confidence_level(cl, self.errordef, 2));
+        let min = &min;
         let idx_x = self
             .names
             .iter()
@@ -1216,7 +1410,7 @@ impl Minuit {
             errordef: self.errordef,
         };
         let contours = MnContours::new(&fcn, min).with_strategy(self.strategy);
-        let mut pts = contours.points(idx_x, idx_y, size);
+        let mut pts = contours.points(idx_x, idx_y, numpoints.or(size).unwrap_or(20));
         if let Some(first) = pts.first().copied() {
             pts.push(first);
         }
@@ -1422,6 +1616,7 @@ impl Minuit {
         let edm_goal = slf.tolerance * slf.errordef * 0.002;
         let is_valid = edm <= edm_goal;
         slf.last_minimum = None;
+        slf.nfcn_offset = 0;
         slf.scan_fmin = Some(FMin {
             fval: best_f,
             edm,
@@ -1440,6 +1635,14 @@ impl Minuit {
 }
 
 impl Minuit {
+    /// User state of the last fit, unless a scan has replaced it.
+    fn fitted_state(&self) -> Option<&crate::MnUserParameterState> {
+        if self.scan_fmin.is_some() {
+            return None;
+        }
+        self.last_minimum.as_ref().map(|m| m.user_state())
+    }
+
     fn build_migrad(&self) -> MnMigrad {
         let mut m = MnMigrad::new()
             .with_strategy(self.strategy)
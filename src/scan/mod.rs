@@ -8,6 +8,7 @@ pub mod minimizer;
 pub mod seed;
 
 use crate::application::default_max_fcn;
+use crate::contours::confidence::chi2_cdf;
 use crate::fcn::FCN;
 use crate::minimum::FunctionMinimum;
 use crate::mn_fcn::MnFcn;
@@ -86,6 +87,67 @@ impl Default for MnScanMinimizer {
     }
 }
 
+/// Result of a 1D parameter scan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanResult {
+    /// `(parameter_value, function_value)` pairs in scan order.
+    pub points: Vec<(f64, f64)>,
+    /// Parameter value at the lowest scanned function value (NaN if empty).
+    pub best_x: f64,
+    /// Lowest scanned function value (NaN if empty).
+    pub best_f: f64,
+    /// Number of scanned points.
+    pub n_points: usize,
+}
+
+impl ScanResult {
+    fn from_points(points: Vec<(f64, f64)>) -> Self {
+        let (best_x, best_f) = points
+            .iter()
+            .copied()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap_or((f64::NAN, f64::NAN));
+        let n_points = points.len();
+        Self {
+            points,
+            best_x,
+            best_f,
+            n_points,
+        }
+    }
+
+    /// Scan point whose parameter value is closest to `param_val`.
+    pub fn closest_to(&self, param_val: f64) -> Option<(f64, f64)> {
+        self.points
+            .iter()
+            .copied()
+            .min_by(|a, b| (a.0 - param_val).abs().total_cmp(&(b.0 - param_val).abs()))
+    }
+
+    /// Function value at `param_val`, linearly interpolated between the two
+    /// neighbouring scan points.
+    ///
+    /// `None` outside the scanned range.
+    pub fn interpolate(&self, param_val: f64) -> Option<f64> {
+        self.points.windows(2).find_map(|w| {
+            let ((x0, f0), (x1, f1)) = (w[0], w[1]);
+            let (lo, hi) = if x0 <= x1 { (x0, x1) } else { (x1, x0) };
+            if !(lo..=hi).contains(&param_val) {
+                return None;
+            }
+            if x1 == x0 {
+                return Some(f0);
+            }
+            Some(f0 + (f1 - f0) * (param_val - x0) / (x1 - x0))
+        })
+    }
+
+    /// Discard the summary and return the raw `(x, f)` points.
+    pub fn into_points(self) -> Vec<(f64, f64)> {
+        self.points
+    }
+}
+
 /// Low-level 1D parameter scan.
 pub struct MnParameterScan<'a, F: FCN + ?Sized> {
     fcn: &'a F,
@@ -100,23 +162,91 @@ impl<'a, F: FCN + ?Sized> MnParameterScan<'a, F> {
 
     /// Scan parameter `par` over `nsteps` points between `low` and `high`.
     ///
-    /// If `low == high == 0.0`, auto-range to +/- 2*error.
-    /// Returns `Vec<(parameter_value, function_value)>`.
+    /// If `low == high == 0.0`, auto-range to +/- 2*error; this form is
+    /// deprecated in favor of [`MnParameterScan::scan_auto`].
+    /// Returns the scanned points together with the best one.
     /// Updates internal fval and param value if a better point is found.
-    pub fn scan(&mut self, par: usize, nsteps: usize, low: f64, high: f64) -> Vec<(f64, f64)> {
+    pub fn scan(&mut self, par: usize, nsteps: usize, low: f64, high: f64) -> ScanResult {
         self.scan_serial(par, nsteps, low, high)
     }
 
-    /// Serial implementation of 1D scan.
-    pub fn scan_serial(
+    /// Scan parameter `par` over `value ± 3 * error` of the current parameters.
+    pub fn scan_auto(&mut self, par: usize, nsteps: usize) -> ScanResult {
+        let p = self.params.trafo().parameter(par);
+        let (value, error) = (p.value(), p.error());
+        self.scan(par, nsteps, value - 3.0 * error, value + 3.0 * error)
+    }
+
+    /// [`MnParameterScan::scan`] with an optional derivative column.
+    ///
+    /// With `include_derivative`, each point also gets the central difference
+    /// `(F(x + h) - F(x - h)) / (2h)` of the scan curve, where `h` is
+    /// `gradient_step` or, if `None`, the parameter error. Near a limit the
+    /// probes are clamped to it and the difference is taken over the clamped
+    /// span. Otherwise the third column is `None`. Returns an empty vector if
+    /// `h` is not positive and finite.
+    pub fn scan_with_step(
         &mut self,
         par: usize,
         nsteps: usize,
         low: f64,
         high: f64,
-    ) -> Vec<(f64, f64)> {
+        gradient_step: Option<f64>,
+        include_derivative: bool,
+    ) -> Vec<(f64, f64, Option<f64>)> {
+        let p = self.params.trafo().parameter(par);
+        let h = gradient_step.unwrap_or_else(|| p.error());
+        if !(h > 0.0 && h.is_finite()) {
+            return Vec::new();
+        }
+        let (lower, upper) = self.clamp_scan_bounds(
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            p.has_lower_limit(),
+            p.lower_limit(),
+            p.has_upper_limit(),
+            p.upper_limit(),
+        );
+        let mut pars: Vec<f64> = self.params.params().iter().map(|p| p.value()).collect();
+        let points = self.scan(par, nsteps, low, high).points;
+        points
+            .into_iter()
+            .map(|(x, f)| {
+                let derivative = include_derivative.then(|| {
+                    let (xp, xm) = ((x + h).min(upper), (x - h).max(lower));
+                    pars[par] = xp;
+                    let fp = self.fcn.value(&pars);
+                    pars[par] = xm;
+                    let fm = self.fcn.value(&pars);
+                    (fp - fm) / (xp - xm)
+                });
+                (x, f, derivative)
+            })
+            .collect()
+    }
+
+    /// [`MnParameterScan::scan`], also reporting whether the best point moved.
+    ///
+    /// The flag is `true` when the scan found a lower function value and
+    /// updated the internal minimum, so scans can be repeated until no
+    /// further improvement is found.
+    pub fn scan_and_update(
+        &mut self,
+        par: usize,
+        nsteps: usize,
+        low: f64,
+        high: f64,
+    ) -> (Vec<(f64, f64)>, bool) {
+        let fval = self.fval;
+        let result = self.scan(par, nsteps, low, high);
+        (result.points, self.fval < fval)
+    }
+
+    /// Serial implementation of 1D scan.
+    pub fn scan_serial(&mut self, par: usize, nsteps: usize, low: f64, high: f64) -> ScanResult {
         let (nsteps, low, high, values) = self.setup_scan(par, nsteps, low, high);
-        let result = self.scan_points(par, nsteps, low, high, values.as_slice());
+        let result =
+            ScanResult::from_points(self.scan_points(par, nsteps, low, high, values.as_slice()));
         self.update_best(par, &result);
 
         result
@@ -124,29 +254,27 @@ impl<'a, F: FCN + ?Sized> MnParameterScan<'a, F> {
 
     /// Parallel implementation of 1D scan (requires `parallel` feature).
     #[cfg(feature = "parallel")]
-    pub fn scan_parallel(
-        &mut self,
-        par: usize,
-        nsteps: usize,
-        low: f64,
-        high: f64,
-    ) -> Vec<(f64, f64)>
+    pub fn scan_parallel(&mut self, par: usize, nsteps: usize, low: f64, high: f64) -> ScanResult
     where
         F: Sync,
     {
         let (nsteps, low, high, values) = self.setup_scan(par, nsteps, low, high);
-        let result = self.scan_points_parallel(par, nsteps, low, high, values.as_slice());
+        let result = ScanResult::from_points(self.scan_points_parallel(
+            par,
+            nsteps,
+            low,
+            high,
+            values.as_slice(),
+        ));
         self.update_best(par, &result);
 
         result
     }
 
-    fn update_best(&mut self, par: usize, result: &[(f64, f64)]) {
-        if let Some((x_best, f_best)) = result.iter().copied().min_by(|a, b| a.1.total_cmp(&b.1))
-            && f_best < self.fval
-        {
-            self.fval = f_best;
-            self.params.set_value(par, x_best);
+    fn update_best(&mut self, par: usize, result: &ScanResult) {
+        if result.best_f < self.fval {
+            self.fval = result.best_f;
+            self.params.set_value(par, result.best_x);
         }
     }
 
@@ -252,6 +380,16 @@ impl<'a, F: FCN + ?Sized> MnParameterScan<'a, F> {
     pub fn params(&self) -> &MnUserParameters {
         &self.params
     }
+
+    /// Best-known parameter values in external space across all scans so far.
+    pub fn best_params(&self) -> Vec<f64> {
+        self.params.params().iter().map(|p| p.value()).collect()
+    }
+
+    /// Best function value across all scans so far (same as `fval`).
+    pub fn best_fval(&self) -> f64 {
+        self.fval
+    }
 }
 
 /// High-level scan builder working with a FunctionMinimum.
@@ -268,21 +406,39 @@ impl<'a, F: FCN + ?Sized> MnScan<'a, F> {
 
     /// Scan parameter `par` over `nsteps` points.
     ///
-    /// If `low == high == 0.0`, auto-range to +/- 2*error.
-    pub fn scan(&self, par: usize, nsteps: usize, low: f64, high: f64) -> Vec<(f64, f64)> {
+    /// If `low == high == 0.0`, auto-range to +/- 2*error; this form is
+    /// deprecated in favor of [`MnScan::scan_n_sigma`].
+    pub fn scan(&self, par: usize, nsteps: usize, low: f64, high: f64) -> ScanResult {
         self.scan_serial(par, nsteps, low, high)
     }
 
     /// Serial scan implementation.
-    pub fn scan_serial(&self, par: usize, nsteps: usize, low: f64, high: f64) -> Vec<(f64, f64)> {
+    pub fn scan_serial(&self, par: usize, nsteps: usize, low: f64, high: f64) -> ScanResult {
         let mut scanner =
             MnParameterScan::new(self.fcn, self.build_user_parameters(), self.minimum.fval());
         scanner.scan_serial(par, nsteps, low, high)
     }
 
+    /// Scan like [`MnScan::scan`] and return the low-level scanner.
+    ///
+    /// The scanner keeps the best point found, so further scans on it share
+    /// the best-point tracking without rebuilding it from the minimum.
+    pub fn scan_internal(
+        &self,
+        par: usize,
+        nsteps: usize,
+        low: f64,
+        high: f64,
+    ) -> MnParameterScan<'a, F> {
+        let mut scanner =
+            MnParameterScan::new(self.fcn, self.build_user_parameters(), self.minimum.fval());
+        scanner.scan(par, nsteps, low, high);
+        scanner
+    }
+
     /// Parallel scan implementation (requires `parallel` feature).
     #[cfg(feature = "parallel")]
-    pub fn scan_parallel(&self, par: usize, nsteps: usize, low: f64, high: f64) -> Vec<(f64, f64)>
+    pub fn scan_parallel(&self, par: usize, nsteps: usize, low: f64, high: f64) -> ScanResult
     where
         F: Sync,
     {
@@ -291,6 +447,301 @@ impl<'a, F: FCN + ?Sized> MnScan<'a, F> {
         scanner.scan_parallel(par, nsteps, low, high)
     }
 
+    /// Parallel scan on a dedicated pool of `n_threads` threads (requires
+    /// `parallel` feature).
+    ///
+    /// Avoids contending with the global rayon pool when called from an
+    /// outer parallel context. `n_threads == 0` lets rayon pick the count;
+    /// if the pool cannot be built the global pool is used instead.
+    #[cfg(feature = "parallel")]
+    pub fn scan_parallel_with_threads(
+        &self,
+        par: usize,
+        nsteps: usize,
+        low: f64,
+        high: f64,
+        n_threads: usize,
+    ) -> ScanResult
+    where
+        F: Sync,
+    {
+        match rayon::ThreadPoolBuilder::new()
+            .num_threads(n_threads)
+            .build()
+        {
+            Ok(pool) => pool.install(|| self.scan_parallel(par, nsteps, low, high)),
+            Err(_) => self.scan_parallel(par, nsteps, low, high),
+        }
+    }
+
+    /// Scan `par` over `value ± half_range` around the fitted value.
+    ///
+    /// Returns an empty result unless `half_range` is positive; use
+    /// [`MnScan::scan`] with `low == high == 0.0` for the automatic range.
+    pub fn scan_symmetric(&self, par: usize, nsteps: usize, half_range: f64) -> ScanResult {
+        if half_range.is_nan() || half_range <= 0.0 {
+            return ScanResult::from_points(Vec::new());
+        }
+        let value = self.minimum.user_state().parameter(par).value();
+        self.scan(par, nsteps, value - half_range, value + half_range)
+    }
+
+    /// Scan `par` over `value ± n * error` around the fitted value.
+    ///
+    /// Empty unless `n * error` is positive, as for [`MnScan::scan_symmetric`].
+    pub fn scan_n_sigma(&self, par: usize, nsteps: usize, n: f64) -> ScanResult {
+        let error = self.minimum.user_state().parameter(par).error();
+        self.scan_symmetric(par, nsteps, n * error)
+    }
+
+    /// [`MnScan::scan_n_sigma`] for the parameter called `name`.
+    ///
+    /// Returns `None` if no parameter has that name.
+    pub fn scan_n_sigma_by_name(&self, name: &str, nsteps: usize, n: f64) -> Option<ScanResult> {
+        let par = self.minimum.user_state().index(name)?;
+        Some(self.scan_n_sigma(par, nsteps, n))
+    }
+
+    /// Conditional scan of `par` with other parameters held at given values.
+    ///
+    /// Each `(index, value)` pair in `fixed` overrides the minimum's value for
+    /// that parameter, giving the profile `F(par | par_j = v_j)`. The
+    /// minimum and the FCN are left untouched.
+    pub fn scan_with_fixed(
+        &self,
+        par: usize,
+        nsteps: usize,
+        low: f64,
+        high: f64,
+        fixed: &[(usize, f64)],
+    ) -> ScanResult {
+        let mut params = self.build_user_parameters();
+        for &(idx, value) in fixed {
+            params.set_value(idx, value);
+        }
+        let mut scanner = MnParameterScan::new(self.fcn, params, self.minimum.fval());
+        scanner.scan_serial(par, nsteps, low, high)
+    }
+
+    /// Scan parameter `par` at the given values instead of a uniform grid.
+    ///
+    /// The values are sorted and duplicates removed before evaluation, so
+    /// e.g. a dense set near the minimum can be merged with the points of an
+    /// earlier coarse scan. Values outside the parameter's limits are
+    /// dropped. Other parameters stay at the minimum.
+    pub fn scan_range(&self, par: usize, values: &[f64]) -> ScanResult {
+        let p = self.minimum.user_state().parameter(par);
+        let mut xs: Vec<f64> = values
+            .iter()
+            .copied()
+            .filter(|&x| {
+                !(p.has_lower_limit() && x < p.lower_limit()
+                    || p.has_upper_limit() && x > p.upper_limit())
+            })
+            .collect();
+        xs.sort_by(f64::total_cmp);
+        xs.dedup();
+
+        let mut pars = self
+            .minimum
+            .user_state()
+            .params()
+            .params()
+            .iter()
+            .map(|p| p.value())
+            .collect::<Vec<_>>();
+        let points = xs
+            .into_iter()
+            .map(|x| {
+                pars[par] = x;
+                (x, self.fcn.value(&pars))
+            })
+            .collect();
+        ScanResult::from_points(points)
+    }
+
+    /// One scan of `nsteps` points per `(low, high)` interval in `ranges`.
+    ///
+    /// Scanning disconnected intervals separately can reveal several local
+    /// minima. The points of all scans are concatenated and sorted by
+    /// parameter value.
+    pub fn scan_best_of_n(
+        &self,
+        par: usize,
+        nsteps: usize,
+        ranges: &[(f64, f64)],
+    ) -> Vec<(f64, f64)> {
+        let mut points: Vec<(f64, f64)> = ranges
+            .iter()
+            .flat_map(|&(low, high)| self.scan(par, nsteps, low, high).points)
+            .collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points
+    }
+
+    /// Scan parameter `par` with points spaced uniformly in `ln(value)`.
+    ///
+    /// Suited to scale parameters spanning orders of magnitude. `low` and
+    /// `high` are linear values and must satisfy `0 < low < high`; they are
+    /// clamped to the parameter limits. Points are reported in linear space.
+    /// An invalid range gives an empty result.
+    pub fn scan_log(&self, par: usize, nsteps: usize, low: f64, high: f64) -> ScanResult {
+        let p = self.minimum.user_state().parameter(par);
+        let low = if p.has_lower_limit() {
+            low.max(p.lower_limit())
+        } else {
+            low
+        };
+        let high = if p.has_upper_limit() {
+            high.min(p.upper_limit())
+        } else {
+            high
+        };
+        if !(low > 0.0 && high > low) {
+            return ScanResult::from_points(Vec::new());
+        }
+
+        let nsteps = nsteps.clamp(2, 101);
+        let (log_lo, log_hi) = (low.ln(), high.ln());
+        let step = (log_hi - log_lo) / nsteps as f64;
+        let mut pars = self
+            .minimum
+            .user_state()
+            .params()
+            .params()
+            .iter()
+            .map(|p| p.value())
+            .collect::<Vec<_>>();
+        let points = (0..=nsteps)
+            .map(|i| {
+                // Pin the end points so rounding in exp(ln(x)) cannot leave the range.
+                let x = match i {
+                    0 => low,
+                    i if i == nsteps => high,
+                    _ => (log_lo + i as f64 * step).exp(),
+                };
+                pars[par] = x;
+                (x, self.fcn.value(&pars))
+            })
+            .collect();
+        ScanResult::from_points(points)
+    }
+
+    /// Scan parameter `par` and add the reduced chi-square of each point.
+    ///
+    /// Returns `(value, fval, fval / (n_data - n_params))` using the same
+    /// range and step conventions as [`MnScan::scan`]. Empty when
+    /// `n_data <= n_params`.
+    pub fn scan_chi2ndf(
+        &self,
+        par: usize,
+        nsteps: usize,
+        low: f64,
+        high: f64,
+        n_data: usize,
+        n_params: usize,
+    ) -> Vec<(f64, f64, f64)> {
+        if n_data <= n_params {
+            return Vec::new();
+        }
+        let ndf = (n_data - n_params) as f64;
+        self.scan(par, nsteps, low, high)
+            .points
+            .into_iter()
+            .map(|(x, f)| (x, f, f / ndf))
+            .collect()
+    }
+
+    /// Scan parameter `par` and add the chi-square p-value of each point.
+    ///
+    /// Returns `(value, chi2, p_value)`, where the FCN value is taken as the
+    /// chi-square and `p_value = 1 - CDF(chi2)` with
+    /// `n_data - n_variable_params` degrees of freedom. Points with a p-value
+    /// above the chosen significance are statistically acceptable. Empty when
+    /// `n_data` does not exceed the number of variable parameters.
+    pub fn scan_pvalue(
+        &self,
+        par: usize,
+        nsteps: usize,
+        low: f64,
+        high: f64,
+        n_data: usize,
+    ) -> Vec<(f64, f64, f64)> {
+        let n_params = self.minimum.n_variable_params();
+        if n_data <= n_params {
+            return Vec::new();
+        }
+        let ndf = n_data - n_params;
+        self.scan(par, nsteps, low, high)
+            .points
+            .into_iter()
+            .map(|(x, chi2)| (x, chi2, 1.0 - chi2_cdf(chi2, ndf)))
+            .collect()
+    }
+
+    /// Scan parameter `par` alongside a model curve for overlay.
+    ///
+    /// Returns `(value, fval, model(value))` using the same range and step
+    /// conventions as [`MnScan::scan`]. A typical model is the asymptotic
+    /// parabola `F_min + (x - x_0)^2 / (2 * sigma^2)` of a Gaussian negative
+    /// log-likelihood.
+    pub fn scan_with_model(
+        &self,
+        par: usize,
+        nsteps: usize,
+        low: f64,
+        high: f64,
+        model: &dyn Fn(f64) -> f64,
+    ) -> Vec<(f64, f64, f64)> {
+        self.scan(par, nsteps, low, high)
+            .points
+            .into_iter()
+            .map(|(x, f)| (x, f, model(x)))
+            .collect()
+    }
+
+    /// Scan parameter `par` and return only the best point `(argmin, fmin)`.
+    ///
+    /// Uses the same range and step conventions as [`MnScan::scan`].
+    pub fn find_minimum(&self, par: usize, nsteps: usize, low: f64, high: f64) -> (f64, f64) {
+        let result = self.scan(par, nsteps, low, high);
+        (result.best_x, result.best_f)
+    }
+
+    /// Parameter values where the scan profile crosses `target_fval`.
+    ///
+    /// Each crossing is located by linear interpolation between the two
+    /// neighbouring scan points that bracket the target. Useful for manually
+    /// locating `fmin + Up` crossings without running MINOS.
+    pub fn find_crossings(
+        &self,
+        par: usize,
+        nsteps: usize,
+        low: f64,
+        high: f64,
+        target_fval: f64,
+    ) -> Vec<f64> {
+        let points = self.scan(par, nsteps, low, high).into_points();
+        let mut crossings = Vec::new();
+        for w in points.windows(2) {
+            let (x0, f0) = w[0];
+            let (x1, f1) = w[1];
+            let d0 = f0 - target_fval;
+            let d1 = f1 - target_fval;
+            if d0 == 0.0 {
+                crossings.push(x0);
+            } else if d0 * d1 < 0.0 {
+                crossings.push(x0 + (x1 - x0) * d0 / (d0 - d1));
+            }
+        }
+        if let Some(&(x, f)) = points.last()
+            && f == target_fval
+        {
+            crossings.push(x);
+        }
+        crossings
+    }
+
     fn build_user_parameters(&self) -> MnUserParameters {
         // Build MnUserParameters from the minimum
         let user_state = self.minimum.user_state();
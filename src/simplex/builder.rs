@@ -20,6 +20,23 @@ impl SimplexBuilder {
         seed: &MinimumSeed,
         maxfcn: usize,
         minedm: f64,
+    ) -> Vec<MinimumState> {
+        Self::minimum_with_min_improvement(fcn, seed, maxfcn, minedm, 0.0)
+    }
+
+    /// [`SimplexBuilder::minimum`] that also stops once an iteration lowers
+    /// the mean vertex value by less than `min_improvement * |fval_best|`.
+    ///
+    /// The mean drops with every accepted step, unlike the best value, so a
+    /// stall means the simplex has stopped making progress even while the
+    /// EDM estimate still fluctuates. `min_improvement <= 0` disables the
+    /// check.
+    pub fn minimum_with_min_improvement(
+        fcn: &MnFcn,
+        seed: &MinimumSeed,
+        maxfcn: usize,
+        minedm: f64,
+        min_improvement: f64,
     ) -> Vec<MinimumState> {
         let n = seed.n_variable_params();
         let prec = seed.precision();
@@ -55,10 +72,16 @@ impl SimplexBuilder {
 
         let mut simplex = SimplexParameters::new(vertices);
         let mut previous_edm;
+        let mut previous_mean;
         loop {
             let worst_index = simplex.jhigh();
             let best_value = simplex.fval_best();
             previous_edm = simplex.edm();
+            previous_mean = Self::mean_fval(&simplex);
+            let stop = |simplex: &SimplexParameters| {
+                Self::should_stop(simplex, previous_edm, minedm, fcn, maxfcn)
+                    || Self::stalled(simplex, previous_mean, min_improvement)
+            };
 
             let centroid = Self::centroid_without(&simplex, worst_index, n);
             let worst_vertex = simplex.params()[worst_index].1.clone();
@@ -69,7 +92,7 @@ impl SimplexBuilder {
                 if reflected_value < simplex.params()[worst_index].0 {
                     simplex.update(worst_index, reflected_value, reflected.clone());
                     if worst_index != simplex.jhigh() {
-                        if !Self::should_stop(&simplex, previous_edm, minedm, fcn, maxfcn) {
+                        if !stop(&simplex) {
                             continue;
                         }
                         break;
@@ -141,7 +164,7 @@ impl SimplexBuilder {
                 }
             }
 
-            if Self::should_stop(&simplex, previous_edm, minedm, fcn, maxfcn) {
+            if stop(&simplex) {
                 break;
             }
         }
@@ -212,6 +235,16 @@ impl SimplexBuilder {
             .collect()
     }
 
+    fn mean_fval(simplex: &SimplexParameters) -> f64 {
+        let vertices = simplex.params();
+        vertices.iter().map(|(f, _)| f).sum::<f64>() / vertices.len() as f64
+    }
+
+    fn stalled(simplex: &SimplexParameters, mean_prev: f64, min_improvement: f64) -> bool {
+        min_improvement > 0.0
+            && mean_prev - Self::mean_fval(simplex) < min_improvement * simplex.fval_best().abs()
+    }
+
     fn should_stop(
         simplex: &SimplexParameters,
         edm_prev: f64,
@@ -16,6 +16,7 @@ use crate::strategy::MnStrategy;
 use crate::user_parameters::MnUserParameters;
 
 /// Builder for configuring and running Simplex minimization.
+#[derive(Clone)]
 pub struct MnSimplex {
     params: MnUserParameters,
     strategy: MnStrategy,
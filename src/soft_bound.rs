@@ -0,0 +1,162 @@
+//! Soft parameter bounds via a quadratic penalty.
+//!
+//! Instead of the sin/sqrt transforms used for hard limits, a soft bound adds
+//! `k * max(0, lower - x)^2 + k * max(0, x - upper)^2` to the FCN. The
+//! internal parameter space stays unbounded and the penalty is smooth, at the
+//! cost of allowing small excursions past the bound.
+
+use crate::fcn::{FCN, FCNGradient, GradientParameterSpace};
+
+/// Quadratic penalty bound on one external parameter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoftBound {
+    /// External parameter index.
+    pub par: usize,
+    pub lower: f64,
+    pub upper: f64,
+    /// Penalty coefficient `k`.
+    pub strength: f64,
+}
+
+impl SoftBound {
+    /// Penalty added to the FCN at parameter value `x`.
+    pub fn penalty(&self, x: f64) -> f64 {
+        let below = (self.lower - x).max(0.0);
+        let above = (x - self.upper).max(0.0);
+        self.strength * (below * below + above * above)
+    }
+
+    /// Derivative of [`SoftBound::penalty`] with respect to `x`.
+    pub fn penalty_derivative(&self, x: f64) -> f64 {
+        let below = (self.lower - x).max(0.0);
+        let above = (x - self.upper).max(0.0);
+        2.0 * self.strength * (above - below)
+    }
+
+    /// Second derivative of [`SoftBound::penalty`] with respect to `x`.
+    pub fn penalty_second_derivative(&self, x: f64) -> f64 {
+        if x < self.lower || x > self.upper {
+            2.0 * self.strength
+        } else {
+            0.0
+        }
+    }
+}
+
+/// FCN wrapper adding soft-bound penalties to the wrapped function.
+pub struct SoftBoundedFCN<'a, F: ?Sized> {
+    fcn: &'a F,
+    bounds: &'a [SoftBound],
+}
+
+impl<'a, F: ?Sized> SoftBoundedFCN<'a, F> {
+    pub fn new(fcn: &'a F, bounds: &'a [SoftBound]) -> Self {
+        Self { fcn, bounds }
+    }
+}
+
+impl<F: FCN + ?Sized> FCN for SoftBoundedFCN<'_, F> {
+    fn value(&self, par: &[f64]) -> f64 {
+        let penalty: f64 = self.bounds.iter().map(|b| b.penalty(par[b.par])).sum();
+        self.fcn.value(par) + penalty
+    }
+
+    fn error_def(&self) -> f64 {
+        self.fcn.error_def()
+    }
+
+    fn has_gradient(&self) -> bool {
+        self.fcn.has_gradient()
+    }
+
+    /// Forwards to the wrapped FCN. Penalty derivatives are added only to
+    /// external-space gradients, since bounds are indexed by external
+    /// parameter.
+    fn gradient_with_prev_result(
+        &self,
+        par: &[f64],
+        previous_grad: Option<&[f64]>,
+        previous_g2: Option<&[f64]>,
+        previous_gstep: Option<&[f64]>,
+    ) -> Vec<f64> {
+        let mut grad =
+            self.fcn
+                .gradient_with_prev_result(par, previous_grad, previous_g2, previous_gstep);
+        if self.grad_parameter_space() == GradientParameterSpace::External && !grad.is_empty() {
+            for b in self.bounds {
+                grad[b.par] += b.penalty_derivative(par[b.par]);
+            }
+        }
+        grad
+    }
+
+    fn grad_parameter_space(&self) -> GradientParameterSpace {
+        self.fcn.grad_parameter_space()
+    }
+
+    fn g2(&self, par: &[f64]) -> Vec<f64> {
+        let mut g2 = self.fcn.g2(par);
+        if !g2.is_empty() {
+            for b in self.bounds {
+                g2[b.par] += b.penalty_second_derivative(par[b.par]);
+            }
+        }
+        g2
+    }
+
+    fn hessian(&self, par: &[f64]) -> Vec<f64> {
+        let mut hessian = self.fcn.hessian(par);
+        if !hessian.is_empty() {
+            // Packed upper triangle: diagonal (i, i) sits at i * (i + 3) / 2.
+            for b in self.bounds {
+                hessian[b.par * (b.par + 3) / 2] += b.penalty_second_derivative(par[b.par]);
+            }
+        }
+        hessian
+    }
+
+    fn has_hessian(&self) -> bool {
+        self.fcn.has_hessian()
+    }
+
+    fn has_g2(&self) -> bool {
+        self.fcn.has_g2()
+    }
+}
+
+impl<F: FCNGradient + ?Sized> FCNGradient for SoftBoundedFCN<'_, F> {
+    fn gradient(&self, par: &[f64]) -> Vec<f64> {
+        let mut grad = self.fcn.gradient(par);
+        for b in self.bounds {
+            grad[b.par] += b.penalty_derivative(par[b.par]);
+        }
+        grad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn penalty_is_zero_inside_and_quadratic_outside() {
+        let b = SoftBound {
+            par: 0,
+            lower: 0.0,
+            upper: 1.0,
+            strength: 10.0,
+        };
+        assert_eq!(b.penalty(0.5), 0.0);
+        assert!((b.penalty(-0.5) - 2.5).abs() < 1e-15);
+        assert!((b.penalty(1.5) - 2.5).abs() < 1e-15);
+        assert!((b.penalty_derivative(-0.5) + 10.0).abs() < 1e-15);
+        assert!((b.penalty_derivative(1.5) - 10.0).abs() < 1e-15);
+        assert_eq!(b.penalty_second_derivative(0.5), 0.0);
+        assert_eq!(b.penalty_second_derivative(1.5), 20.0);
+
+        let f = |p: &[f64]| p[0];
+        let bounds = [b];
+        let wrapped = SoftBoundedFCN::new(&f, &bounds);
+        assert!((wrapped.value(&[2.0]) - 12.0).abs() < 1e-15);
+    }
+}
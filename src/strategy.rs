@@ -1,14 +1,55 @@
+use std::fmt;
+
+/// A strategy setting that would stall or break the derivative loops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyValidationError {
+    /// `grad_ncycles` is zero.
+    GradNcycles,
+    /// `hess_ncycles` is zero.
+    HessNcycles,
+    /// `hess_grad_ncycles` is zero.
+    HessGradNcycles,
+    /// `grad_step_tol` is not positive.
+    GradStepTol,
+    /// `grad_tol` is not positive.
+    GradTol,
+    /// `hess_step_tol` is not positive.
+    HessStepTol,
+    /// `hess_g2_tol` is not positive.
+    HessG2Tol,
+}
+
+impl fmt::Display for StrategyValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::GradNcycles => "grad_ncycles must be >= 1",
+            Self::HessNcycles => "hess_ncycles must be >= 1",
+            Self::HessGradNcycles => "hess_grad_ncycles must be >= 1",
+            Self::GradStepTol => "grad_step_tol must be > 0",
+            Self::GradTol => "grad_tol must be > 0",
+            Self::HessStepTol => "hess_step_tol must be > 0",
+            Self::HessG2Tol => "hess_g2_tol must be > 0",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for StrategyValidationError {}
+
 /// Strategy presets controlling gradient/Hessian calculation effort.
 ///
-/// Three Minuit-style effort levels: low (0), medium (1), high (2).
-/// Medium is the default.
+/// Minuit-style effort levels: low (0), medium (1), high (2) and very high
+/// (3). Medium is the default.
+///
+/// Very high spends more gradient and Hesse cycles with tighter tolerances;
+/// it suits final, publication-quality fits where FCN cost is not limiting.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MnStrategy {
     strategy: u32,
     grad_ncycles: u32,
     hess_ncycles: u32,
     hess_grad_ncycles: u32,
-    grad_step_tol: u32, // stored as x10 to avoid float equality issues
+    grad_step_tol: u32, // stored as x100 to avoid float equality issues
     grad_tol: u32,      // stored as x100
     hess_step_tol: u32, // stored as x10
     hess_g2_tol: u32,   // stored as x100
@@ -18,7 +59,8 @@ pub struct MnStrategy {
 }
 
 impl MnStrategy {
-    /// Create a new strategy with the given level (0=low, 1=medium, 2=high).
+    /// Create a new strategy with the given level (0=low, 1=medium, 2=high,
+    /// 3=very high).
     pub fn new(level: u32) -> Self {
         let mut s = Self {
             strategy: level,
@@ -36,15 +78,33 @@ impl MnStrategy {
         match level {
             0 => s.set_low_strategy(),
             2 => s.set_high_strategy(),
+            3 => s.set_very_high_strategy(),
             _ => s.set_medium_strategy(),
         }
         s
     }
 
+    /// Pick a strategy level from the number of variable parameters.
+    ///
+    /// Heuristic from the Minuit2 manual: the Hessian costs O(n²) calls, so
+    /// - `n <= 10`: high (2),
+    /// - `10 < n <= 30`: medium (1),
+    /// - `n > 30`: low (0).
+    ///
+    /// Override with [`MnStrategy::new`] when the FCN is cheap enough for a
+    /// higher level, or when accurate errors matter more than run time.
+    pub fn for_ndim(n: usize) -> Self {
+        match n {
+            0..=10 => Self::new(2),
+            11..=30 => Self::new(1),
+            _ => Self::new(0),
+        }
+    }
+
     fn set_low_strategy(&mut self) {
         self.strategy = 0;
         self.grad_ncycles = 2;
-        self.grad_step_tol = 5; // 0.5
+        self.grad_step_tol = 50; // 0.5
         self.grad_tol = 10; // 0.1
         self.hess_ncycles = 3;
         self.hess_step_tol = 5; // 0.5
@@ -58,7 +118,7 @@ impl MnStrategy {
     fn set_medium_strategy(&mut self) {
         self.strategy = 1;
         self.grad_ncycles = 3;
-        self.grad_step_tol = 3; // 0.3
+        self.grad_step_tol = 30; // 0.3
         self.grad_tol = 5; // 0.05
         self.hess_ncycles = 5;
         self.hess_step_tol = 3; // 0.3
@@ -72,7 +132,7 @@ impl MnStrategy {
     fn set_high_strategy(&mut self) {
         self.strategy = 2;
         self.grad_ncycles = 5;
-        self.grad_step_tol = 1; // 0.1
+        self.grad_step_tol = 10; // 0.1
         self.grad_tol = 2; // 0.02
         self.hess_ncycles = 7;
         self.hess_step_tol = 1; // 0.1
@@ -99,7 +159,7 @@ impl MnStrategy {
 
     /// Get the gradient step tolerance.
     pub fn grad_step_tol(&self) -> f64 {
-        self.grad_step_tol as f64 / 10.0
+        self.grad_step_tol as f64 / 100.0
     }
 
     pub fn gradient_step_tolerance(&self) -> f64 {
@@ -168,7 +228,7 @@ impl MnStrategy {
     }
 
     pub fn set_gradient_step_tolerance(&mut self, tol: f64) {
-        self.grad_step_tol = (tol.max(0.0) * 10.0).round() as u32;
+        self.grad_step_tol = (tol.max(0.0) * 100.0).round() as u32;
     }
 
     pub fn set_gradient_tolerance(&mut self, tol: f64) {
@@ -203,6 +263,40 @@ impl MnStrategy {
         self.store_level = level;
     }
 
+    /// Check that cycle counts are at least 1 and tolerances positive.
+    ///
+    /// Returns every violation at once.
+    pub fn validate(&self) -> Result<(), Vec<StrategyValidationError>> {
+        let checks = [
+            (self.grad_ncycles >= 1, StrategyValidationError::GradNcycles),
+            (self.hess_ncycles >= 1, StrategyValidationError::HessNcycles),
+            (
+                self.hess_grad_ncycles >= 1,
+                StrategyValidationError::HessGradNcycles,
+            ),
+            (
+                self.grad_step_tol() > 0.0,
+                StrategyValidationError::GradStepTol,
+            ),
+            (self.grad_tol() > 0.0, StrategyValidationError::GradTol),
+            (
+                self.hess_step_tol() > 0.0,
+                StrategyValidationError::HessStepTol,
+            ),
+            (self.hess_g2_tol() > 0.0, StrategyValidationError::HessG2Tol),
+        ];
+        let errors: Vec<_> = checks
+            .into_iter()
+            .filter(|(ok, _)| !ok)
+            .map(|(_, err)| err)
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Check if this is a low strategy.
     pub fn is_low(&self) -> bool {
         self.strategy == 0
@@ -223,8 +317,17 @@ impl MnStrategy {
     }
 
     pub fn set_very_high_strategy(&mut self) {
-        self.set_high_strategy();
         self.strategy = 3;
+        self.grad_ncycles = 8;
+        self.grad_step_tol = 5; // 0.05
+        self.grad_tol = 2; // 0.02
+        self.hess_ncycles = 10;
+        self.hess_step_tol = 1; // 0.1
+        self.hess_g2_tol = 1; // 0.01
+        self.hess_grad_ncycles = 8;
+        self.hess_cfd_g2 = 0;
+        self.hess_force_pos_def = 1;
+        self.store_level = 1;
     }
 }
 
@@ -271,6 +374,26 @@ mod tests {
         assert_eq!(s.hess_grad_ncycles(), 6);
     }
 
+    #[test]
+    fn validate_reports_all_violations() {
+        for level in 0..=3 {
+            assert_eq!(MnStrategy::new(level).validate(), Ok(()));
+        }
+
+        let mut s = MnStrategy::new(1);
+        s.set_gradient_ncycles(0);
+        s.set_hessian_g2_tolerance(0.0);
+        s.set_hessian_step_tolerance(0.01); // rounds to zero at x10 storage
+        assert_eq!(
+            s.validate(),
+            Err(vec![
+                StrategyValidationError::GradNcycles,
+                StrategyValidationError::HessStepTol,
+                StrategyValidationError::HessG2Tol,
+            ])
+        );
+    }
+
     #[test]
     fn default_is_medium() {
         let s = MnStrategy::default();
@@ -316,6 +439,16 @@ mod tests {
         assert_eq!(s.storage_level(), 3);
     }
 
+    #[test]
+    fn for_ndim_thresholds() {
+        assert_eq!(MnStrategy::for_ndim(1).strategy(), 2);
+        assert_eq!(MnStrategy::for_ndim(10).strategy(), 2);
+        assert_eq!(MnStrategy::for_ndim(11).strategy(), 1);
+        assert_eq!(MnStrategy::for_ndim(30).strategy(), 1);
+        assert_eq!(MnStrategy::for_ndim(31).strategy(), 0);
+        assert_eq!(MnStrategy::for_ndim(50), MnStrategy::new(0));
+    }
+
     #[test]
     fn very_high_strategy() {
         let mut s = MnStrategy::new(1);
@@ -323,5 +456,14 @@ mod tests {
         s.set_very_high_strategy();
         assert!(s.is_very_high());
         assert!(s.is_high());
+        assert_eq!(s, MnStrategy::new(3));
+
+        assert_eq!(s.grad_ncycles(), 8);
+        assert!((s.grad_step_tol() - 0.05).abs() < 1e-15);
+        assert!((s.grad_tol() - 0.02).abs() < 1e-15);
+        assert_eq!(s.hess_ncycles(), 10);
+        assert!((s.hess_g2_tol() - 0.01).abs() < 1e-15);
+        assert_eq!(s.hess_grad_ncycles(), 8);
+        assert_ne!(s, MnStrategy::new(2));
     }
 }
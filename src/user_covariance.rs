@@ -1,3 +1,46 @@
+use std::fmt;
+
+use nalgebra::DMatrix;
+
+/// Reason a covariance could not be built from user input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CovarianceError {
+    /// The correlation matrix is not `n×n` for `n` errors.
+    DimensionMismatch,
+    /// Diagonal element `index` of the correlation matrix is not 1.
+    DiagonalNotOne { index: usize },
+    /// Off-diagonal element `(row, col)` lies outside `[-1, 1]`.
+    CorrelationOutOfRange { row: usize, col: usize },
+    /// Elements `(row, col)` and `(col, row)` of the correlation matrix differ.
+    NotSymmetric { row: usize, col: usize },
+    /// Error `index` is negative or not finite.
+    InvalidError { index: usize },
+}
+
+impl fmt::Display for CovarianceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DimensionMismatch => {
+                write!(f, "correlation matrix size does not match error vector")
+            }
+            Self::DiagonalNotOne { index } => {
+                write!(f, "correlation diagonal element {index} is not 1")
+            }
+            Self::CorrelationOutOfRange { row, col } => {
+                write!(f, "correlation ({row}, {col}) is outside [-1, 1]")
+            }
+            Self::NotSymmetric { row, col } => {
+                write!(f, "correlation ({row}, {col}) differs from ({col}, {row})")
+            }
+            Self::InvalidError { index } => {
+                write!(f, "error {index} is negative or not finite")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CovarianceError {}
+
 /// User-level covariance matrix stored as upper triangle.
 ///
 /// The C++ `MnUserCovariance` stores an n×n symmetric matrix as n*(n+1)/2
@@ -23,6 +66,46 @@ impl MnUserCovariance {
         Self { data, nrow: n }
     }
 
+    /// Build from a correlation matrix and per-parameter errors.
+    ///
+    /// `Cov[i][j] = corr[i][j] * errors[i] * errors[j]`. `corr` must be
+    /// symmetric to 1e-12 with a unit diagonal and off-diagonal elements in
+    /// `[-1, 1]`; `errors` must be finite and non-negative.
+    pub fn from_correlation_and_errors(
+        corr: &[Vec<f64>],
+        errors: &[f64],
+    ) -> Result<Self, CovarianceError> {
+        let n = errors.len();
+        if corr.len() != n || corr.iter().any(|row| row.len() != n) {
+            return Err(CovarianceError::DimensionMismatch);
+        }
+
+        if let Some(index) = errors.iter().position(|e| !e.is_finite() || *e < 0.0) {
+            return Err(CovarianceError::InvalidError { index });
+        }
+
+        let mut cov = Self::new(n);
+        for i in 0..n {
+            let diag_dev = (corr[i][i] - 1.0).abs();
+            if diag_dev.is_nan() || diag_dev > 1e-12 {
+                return Err(CovarianceError::DiagonalNotOne { index: i });
+            }
+            cov.set(i, i, errors[i] * errors[i]);
+            for j in (i + 1)..n {
+                let rho = corr[i][j];
+                if !(-1.0..=1.0).contains(&rho) {
+                    return Err(CovarianceError::CorrelationOutOfRange { row: i, col: j });
+                }
+                let asym = (corr[j][i] - rho).abs();
+                if asym.is_nan() || asym > 1e-12 {
+                    return Err(CovarianceError::NotSymmetric { row: j, col: i });
+                }
+                cov.set(i, j, rho * errors[i] * errors[j]);
+            }
+        }
+        Ok(cov)
+    }
+
     /// Get the number of rows (parameters).
     pub fn nrow(&self) -> usize {
         self.nrow
@@ -58,6 +141,62 @@ impl MnUserCovariance {
     pub fn size(&self) -> usize {
         self.data.len()
     }
+
+    /// Matrix inverse, i.e. the Hessian in the same scale as this covariance.
+    ///
+    /// `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<MnUserCovariance> {
+        let inv = self.to_matrix().try_inverse()?;
+        let mut out = MnUserCovariance::new(self.nrow);
+        for c in 0..self.nrow {
+            for r in 0..=c {
+                out.set(r, c, inv[(r, c)]);
+            }
+        }
+        Some(out)
+    }
+
+    /// Lower Cholesky factor `L` with `V = L * L^T`.
+    ///
+    /// Packed like [`MnUserCovariance::data`] read as a lower triangle:
+    /// `L(i, j)` for `j <= i` is at index `j + i * (i + 1) / 2`. `None` if
+    /// the matrix is not positive-definite.
+    pub fn cholesky(&self) -> Option<Vec<f64>> {
+        let l = self.to_matrix().cholesky()?.unpack();
+        let mut out = Vec::with_capacity(self.data.len());
+        for i in 0..self.nrow {
+            for j in 0..=i {
+                out.push(l[(i, j)]);
+            }
+        }
+        Some(out)
+    }
+
+    fn to_matrix(&self) -> DMatrix<f64> {
+        DMatrix::from_fn(self.nrow, self.nrow, |r, c| self.get(r, c))
+    }
+
+    /// Replace the diagonal with `errors[i]^2`, keeping the correlation structure.
+    ///
+    /// Off-diagonal elements are rescaled by `errors[i] * errors[j] / (σ_i σ_j)`
+    /// so that every correlation coefficient is unchanged. Off-diagonal
+    /// elements involving a zero old variance are set to zero.
+    pub fn update_diagonal_from_errors(&mut self, errors: &[f64]) {
+        assert_eq!(errors.len(), self.nrow, "errors size mismatch");
+        let old_sigma: Vec<f64> = (0..self.nrow).map(|i| self.get(i, i).sqrt()).collect();
+        for i in 0..self.nrow {
+            for j in (i + 1)..self.nrow {
+                let denom = old_sigma[i] * old_sigma[j];
+                let val = if denom > 0.0 {
+                    self.get(i, j) * errors[i] * errors[j] / denom
+                } else {
+                    0.0
+                };
+                self.set(i, j, val);
+            }
+            self.set(i, i, errors[i] * errors[i]);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -86,4 +225,86 @@ mod tests {
         let cov = MnUserCovariance::new(4);
         assert_eq!(cov.data().len(), 10); // 4*5/2
     }
+
+    #[test]
+    fn from_correlation_and_errors() {
+        let corr = vec![vec![1.0, -0.5], vec![-0.5, 1.0]];
+        let cov = MnUserCovariance::from_correlation_and_errors(&corr, &[2.0, 3.0]).unwrap();
+        assert!((cov.get(0, 0) - 4.0).abs() < 1e-15);
+        assert!((cov.get(1, 1) - 9.0).abs() < 1e-15);
+        assert!((cov.get(1, 0) + 3.0).abs() < 1e-15);
+
+        let bad_diag = vec![vec![0.9, 0.0], vec![0.0, 1.0]];
+        assert_eq!(
+            MnUserCovariance::from_correlation_and_errors(&bad_diag, &[1.0, 1.0]).unwrap_err(),
+            CovarianceError::DiagonalNotOne { index: 0 }
+        );
+        let bad_rho = vec![vec![1.0, 1.5], vec![1.5, 1.0]];
+        assert_eq!(
+            MnUserCovariance::from_correlation_and_errors(&bad_rho, &[1.0, 1.0]).unwrap_err(),
+            CovarianceError::CorrelationOutOfRange { row: 0, col: 1 }
+        );
+        assert_eq!(
+            MnUserCovariance::from_correlation_and_errors(&corr, &[1.0]).unwrap_err(),
+            CovarianceError::DimensionMismatch
+        );
+    }
+
+    #[test]
+    fn from_correlation_and_errors_rejects_asymmetry() {
+        let flipped = vec![vec![1.0, 0.9], vec![-0.9, 1.0]];
+        assert_eq!(
+            MnUserCovariance::from_correlation_and_errors(&flipped, &[1.0, 1.0]).unwrap_err(),
+            CovarianceError::NotSymmetric { row: 1, col: 0 }
+        );
+        let lower_out_of_range = vec![vec![1.0, 0.5], vec![1.5, 1.0]];
+        assert_eq!(
+            MnUserCovariance::from_correlation_and_errors(&lower_out_of_range, &[1.0, 1.0])
+                .unwrap_err(),
+            CovarianceError::NotSymmetric { row: 1, col: 0 }
+        );
+        let lower_nan = vec![vec![1.0, 0.5], vec![f64::NAN, 1.0]];
+        assert!(MnUserCovariance::from_correlation_and_errors(&lower_nan, &[1.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn from_correlation_and_errors_rejects_invalid_errors() {
+        let corr = vec![vec![1.0, 0.2], vec![0.2, 1.0]];
+        for bad in [-1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                MnUserCovariance::from_correlation_and_errors(&corr, &[1.0, bad]).unwrap_err(),
+                CovarianceError::InvalidError { index: 1 }
+            );
+        }
+        assert!(MnUserCovariance::from_correlation_and_errors(&corr, &[0.0, 1.0]).is_ok());
+    }
+
+    #[test]
+    fn inverse_and_cholesky() {
+        // V = [[4, 2], [2, 5]], V^-1 = [[5, -2], [-2, 4]] / 16, L = [[2, 0], [1, 2]].
+        let cov = MnUserCovariance::from_vec(vec![4.0, 2.0, 5.0], 2);
+        let hessian = cov.inverse().unwrap();
+        assert!((hessian.get(0, 0) - 5.0 / 16.0).abs() < 1e-15);
+        assert!((hessian.get(0, 1) + 2.0 / 16.0).abs() < 1e-15);
+        assert!((hessian.get(1, 1) - 4.0 / 16.0).abs() < 1e-15);
+        assert_eq!(cov.cholesky().unwrap(), vec![2.0, 1.0, 2.0]);
+
+        let singular = MnUserCovariance::from_vec(vec![1.0, 1.0, 1.0], 2);
+        assert!(singular.inverse().is_none());
+        let indefinite = MnUserCovariance::from_vec(vec![1.0, 2.0, 1.0], 2);
+        assert!(indefinite.cholesky().is_none());
+    }
+
+    #[test]
+    fn update_diagonal_preserves_correlation() {
+        let mut cov = MnUserCovariance::new(2);
+        cov.set(0, 0, 4.0);
+        cov.set(1, 1, 9.0);
+        cov.set(0, 1, 3.0); // rho = 3 / (2 * 3) = 0.5
+        cov.update_diagonal_from_errors(&[1.0, 5.0]);
+        assert!((cov.get(0, 0) - 1.0).abs() < 1e-15);
+        assert!((cov.get(1, 1) - 25.0).abs() < 1e-15);
+        let rho = cov.get(0, 1) / (cov.get(0, 0) * cov.get(1, 1)).sqrt();
+        assert!((rho - 0.5).abs() < 1e-15);
+    }
 }
@@ -3,10 +3,52 @@
 //! State object returned to the user after minimization, containing fitted
 //! values, errors, and optionally the covariance matrix.
 
+use std::collections::HashMap;
+use std::fmt;
+
 use crate::parameter::MinuitParameter;
 use crate::user_covariance::MnUserCovariance;
 use crate::user_parameters::MnUserParameters;
 
+/// Leading bytes of [`MnUserParameterState::pack`] output.
+const PACK_MAGIC: [u8; 4] = *b"MNUS";
+/// Current [`MnUserParameterState::pack`] format version.
+const PACK_VERSION: u32 = 1;
+
+const FLAG_LOWER: u8 = 1;
+const FLAG_UPPER: u8 = 1 << 1;
+const FLAG_FIXED: u8 = 1 << 2;
+const FLAG_CONST: u8 = 1 << 3;
+
+/// Errors from [`MnUserParameterState::unpack`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnpackError {
+    /// The data does not start with the pack magic bytes.
+    BadMagic,
+    /// The data was written by an unknown format version.
+    UnsupportedVersion(u32),
+    /// The data ends before the encoded state is complete.
+    Truncated,
+    /// A parameter name is not valid UTF-8.
+    InvalidName,
+    /// Bytes remain after the encoded state.
+    TrailingBytes,
+}
+
+impl fmt::Display for UnpackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a packed parameter state"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported pack version {v}"),
+            Self::Truncated => write!(f, "packed parameter state is truncated"),
+            Self::InvalidName => write!(f, "parameter name is not valid UTF-8"),
+            Self::TrailingBytes => write!(f, "trailing bytes after packed parameter state"),
+        }
+    }
+}
+
+impl std::error::Error for UnpackError {}
+
 #[derive(Debug, Clone)]
 pub struct MnUserParameterState {
     params: MnUserParameters,
@@ -117,6 +159,15 @@ impl MnUserParameterState {
         self.global_cc.as_deref()
     }
 
+    /// Global correlation coefficient of the parameter named `name`.
+    ///
+    /// `None` if the name is unknown, the parameter is fixed, or no global
+    /// correlations are available.
+    pub fn global_cc_by_name(&self, name: &str) -> Option<f64> {
+        let int = self.int_of_ext(self.index(name)?)?;
+        self.global_cc.as_ref()?.get(int).copied()
+    }
+
     pub fn set_global_cc(&mut self, gcc: Vec<f64>) {
         self.global_cc = Some(gcc);
     }
@@ -178,6 +229,28 @@ impl MnUserParameterState {
         self.params.set_precision(eps);
     }
 
+    /// Set the values of the named parameters from `new_values`.
+    ///
+    /// Names not present in the state are ignored.
+    pub fn update_from_params(&mut self, new_values: &HashMap<String, f64>) {
+        for (name, &value) in new_values {
+            if let Some(ext) = self.index(name) {
+                self.set_value(ext, value);
+            }
+        }
+    }
+
+    /// Set the errors of the named parameters from `new_errors`.
+    ///
+    /// Names not present in the state are ignored.
+    pub fn update_errors_from_map(&mut self, new_errors: &HashMap<String, f64>) {
+        for (name, &error) in new_errors {
+            if let Some(ext) = self.index(name) {
+                self.set_error(ext, error);
+            }
+        }
+    }
+
     pub fn value(&self, name: &str) -> Option<f64> {
         self.params.value(name)
     }
@@ -226,12 +299,149 @@ impl MnUserParameterState {
     pub fn is_empty(&self) -> bool {
         self.params.is_empty()
     }
+
+    /// Encode the parameters, fval and EDM in a stable binary format.
+    ///
+    /// Layout, all little-endian: magic `b"MNUS"`, `u32` version, `f64` fval,
+    /// `f64` EDM, `u32` parameter count, then per parameter a `u32`-length
+    /// UTF-8 name, `f64` value, error, lower and upper limit, and a `u8` of
+    /// limit/fixed/const flags. The covariance and call count are not stored.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&PACK_MAGIC);
+        out.extend_from_slice(&PACK_VERSION.to_le_bytes());
+        out.extend_from_slice(&self.fval.to_le_bytes());
+        out.extend_from_slice(&self.edm.to_le_bytes());
+        out.extend_from_slice(&(self.len() as u32).to_le_bytes());
+        for p in self.params.params() {
+            out.extend_from_slice(&(p.name().len() as u32).to_le_bytes());
+            out.extend_from_slice(p.name().as_bytes());
+            for x in [p.value(), p.error(), p.lower_limit(), p.upper_limit()] {
+                out.extend_from_slice(&x.to_le_bytes());
+            }
+            let mut flags = 0;
+            if p.has_lower_limit() {
+                flags |= FLAG_LOWER;
+            }
+            if p.has_upper_limit() {
+                flags |= FLAG_UPPER;
+            }
+            if p.is_fixed() {
+                flags |= FLAG_FIXED;
+            }
+            if p.is_const() {
+                flags |= FLAG_CONST;
+            }
+            out.push(flags);
+        }
+        out
+    }
+
+    /// Decode a state written by [`MnUserParameterState::pack`].
+    pub fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = PackReader { data };
+        if reader.take(4)? != PACK_MAGIC {
+            return Err(UnpackError::BadMagic);
+        }
+        let version = reader.u32()?;
+        if version != PACK_VERSION {
+            return Err(UnpackError::UnsupportedVersion(version));
+        }
+        let fval = reader.f64()?;
+        let edm = reader.f64()?;
+        let n = reader.u32()?;
+
+        let mut params = MnUserParameters::new();
+        for _ in 0..n {
+            let len = reader.u32()? as usize;
+            let name =
+                std::str::from_utf8(reader.take(len)?).map_err(|_| UnpackError::InvalidName)?;
+            let (value, error) = (reader.f64()?, reader.f64()?);
+            let (lower, upper) = (reader.f64()?, reader.f64()?);
+            let flags = reader.take(1)?[0];
+
+            let ext = if flags & FLAG_CONST != 0 {
+                params.add_const(name, value)
+            } else {
+                match (flags & FLAG_LOWER != 0, flags & FLAG_UPPER != 0) {
+                    (true, true) => params.add_limited(name, value, error, lower, upper),
+                    (true, false) => params.add_lower_limited(name, value, error, lower),
+                    (false, true) => params.add_upper_limited(name, value, error, upper),
+                    (false, false) => params.add(name, value, error),
+                }
+            };
+            if flags & FLAG_FIXED != 0 && flags & FLAG_CONST == 0 {
+                params.fix(ext);
+            }
+        }
+        if !reader.data.is_empty() {
+            return Err(UnpackError::TrailingBytes);
+        }
+
+        let mut state = Self::new(params);
+        state.fval = fval;
+        state.edm = edm;
+        Ok(state)
+    }
+
+    /// Independent copy of the parameters, covariance, and fit summary.
+    ///
+    /// See [`MnUserParameters::snapshot`].
+    pub fn snapshot(&self) -> MnUserParameterState {
+        self.clone()
+    }
+}
+
+/// Cursor over [`MnUserParameterState::pack`] output.
+struct PackReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PackReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], UnpackError> {
+        if self.data.len() < n {
+            return Err(UnpackError::Truncated);
+        }
+        let (head, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, UnpackError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn f64(&mut self) -> Result<f64, UnpackError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(f64::from_le_bytes(bytes))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn update_values_and_errors_from_maps() {
+        let mut params = MnUserParameters::new();
+        params.add("x", 1.0, 0.1);
+        params.add("y", 2.0, 0.2);
+        let mut state = MnUserParameterState::new(params);
+
+        let values = HashMap::from([("y".to_string(), -3.0), ("z".to_string(), 9.0)]);
+        state.update_from_params(&values);
+        let errors = HashMap::from([("x".to_string(), 0.5)]);
+        state.update_errors_from_map(&errors);
+
+        assert_eq!(state.value("x"), Some(1.0));
+        assert_eq!(state.value("y"), Some(-3.0));
+        assert_eq!(state.error("x"), Some(0.5));
+        assert_eq!(state.error("y"), Some(0.2));
+        assert_eq!(state.len(), 2);
+    }
+
     #[test]
     fn state_delegates_limit_operations() {
         let mut params = MnUserParameters::new();
@@ -255,6 +465,37 @@ mod tests {
         assert_eq!(state.index("x"), None);
     }
 
+    #[test]
+    fn state_snapshot_is_independent_of_original() {
+        let mut params = MnUserParameters::new();
+        params.add("x", 1.0, 0.1);
+        let mut state = MnUserParameterState::new(params);
+        state.set_limits(0, -1.0, 2.0);
+        let snap = state.snapshot();
+
+        state.remove_limits(0);
+        state.fix(0);
+        assert!(snap.parameter(0).has_limits());
+        assert!(!snap.parameter(0).is_fixed());
+    }
+
+    #[test]
+    fn global_cc_by_name_skips_fixed() {
+        let mut params = MnUserParameters::new();
+        params.add("x", 1.0, 0.1);
+        params.add("c", 2.0, 0.1);
+        params.add("y", 3.0, 0.1);
+        params.fix(1);
+        let mut state = MnUserParameterState::new(params);
+        assert_eq!(state.global_cc_by_name("x"), None);
+
+        state.set_global_cc(vec![0.25, 0.75]);
+        assert_eq!(state.global_cc_by_name("x"), Some(0.25));
+        assert_eq!(state.global_cc_by_name("y"), Some(0.75));
+        assert_eq!(state.global_cc_by_name("c"), None);
+        assert_eq!(state.global_cc_by_name("z"), None);
+    }
+
     #[test]
     fn state_internal_external_mapping() {
         let mut params = MnUserParameters::new();
@@ -266,4 +507,67 @@ mod tests {
         assert!((state.ext2int(0, 3.0) - 3.0).abs() < 1e-15);
         assert!((state.int2ext(0, 3.0) - 3.0).abs() < 1e-15);
     }
+
+    #[test]
+    fn pack_unpack_roundtrip() {
+        let mut params = MnUserParameters::new();
+        params.add("x", 1.5, 0.1);
+        params.add_limited("y", 0.5, 0.2, 0.0, 1.0);
+        params.add_lower_limited("z", 2.0, 0.3, -1.0);
+        params.add_upper_limited("w", -2.0, 0.4, 3.0);
+        params.add_const("c", 7.0);
+        params.fix(0);
+        let mut state = MnUserParameterState::new(params);
+        state.set_fval(12.25);
+        state.set_edm(1e-6);
+
+        let restored = MnUserParameterState::unpack(&state.pack()).unwrap();
+        assert_eq!(restored.fval(), 12.25);
+        assert_eq!(restored.edm(), 1e-6);
+        assert_eq!(restored.len(), state.len());
+        for (a, b) in state
+            .params()
+            .params()
+            .iter()
+            .zip(restored.params().params())
+        {
+            assert_eq!(a.name(), b.name());
+            assert_eq!(a.value(), b.value());
+            assert_eq!(a.error(), b.error());
+            assert_eq!(a.has_lower_limit(), b.has_lower_limit());
+            assert_eq!(a.has_upper_limit(), b.has_upper_limit());
+            assert_eq!(a.lower_limit(), b.lower_limit());
+            assert_eq!(a.upper_limit(), b.upper_limit());
+            assert_eq!(a.is_fixed(), b.is_fixed());
+            assert_eq!(a.is_const(), b.is_const());
+        }
+    }
+
+    #[test]
+    fn unpack_rejects_malformed_data() {
+        let mut params = MnUserParameters::new();
+        params.add("x", 1.0, 0.1);
+        let packed = MnUserParameterState::new(params).pack();
+
+        assert_eq!(
+            MnUserParameterState::unpack(b"nope").unwrap_err(),
+            UnpackError::BadMagic
+        );
+        assert_eq!(
+            MnUserParameterState::unpack(&packed[..packed.len() - 1]).unwrap_err(),
+            UnpackError::Truncated
+        );
+        let mut trailing = packed.clone();
+        trailing.push(0);
+        assert_eq!(
+            MnUserParameterState::unpack(&trailing).unwrap_err(),
+            UnpackError::TrailingBytes
+        );
+        let mut future = packed;
+        future[4..8].copy_from_slice(&2u32.to_le_bytes());
+        assert_eq!(
+            MnUserParameterState::unpack(&future).unwrap_err(),
+            UnpackError::UnsupportedVersion(2)
+        );
+    }
 }
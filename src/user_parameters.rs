@@ -4,22 +4,50 @@
 //! the user-facing API for adding, fixing, releasing, and bounding parameters.
 
 use std::collections::HashMap;
+use std::fmt;
 
 use crate::parameter::MinuitParameter;
 use crate::user_transformation::MnUserTransformation;
 
+/// Parameter names that occur more than once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateNameError {
+    /// Each duplicated name, once, in order of first occurrence.
+    pub names: Vec<String>,
+}
+
+impl fmt::Display for DuplicateNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "duplicate parameter names: {}", self.names.join(", "))
+    }
+}
+
+impl std::error::Error for DuplicateNameError {}
+
 #[derive(Debug, Clone)]
 pub struct MnUserParameters {
     trafo: MnUserTransformation,
     name_map: HashMap<String, usize>,
+    deny_duplicates: bool,
 }
 
 impl MnUserParameters {
     /// Create an empty parameter collection.
+    ///
+    /// Duplicate names are accepted; the last one added wins in name lookups.
     pub fn new() -> Self {
         Self {
             trafo: MnUserTransformation::new(Vec::new()),
             name_map: HashMap::new(),
+            deny_duplicates: false,
+        }
+    }
+
+    /// Create an empty collection that panics when a name is reused.
+    pub fn strict() -> Self {
+        Self {
+            deny_duplicates: true,
+            ..Self::new()
         }
     }
 
@@ -36,10 +64,7 @@ impl MnUserParameters {
     pub fn add(&mut self, name: impl Into<String>, value: f64, error: f64) -> usize {
         let name = name.into();
         let ext = self.trafo.parameters_len();
-        let param = MinuitParameter::new(ext, &name, value, error);
-        self.trafo.add(param);
-        self.name_map.insert(name, ext);
-        ext
+        self.push(MinuitParameter::new(ext, &name, value, error), name)
     }
 
     /// Add a parameter with both bounds.
@@ -53,10 +78,10 @@ impl MnUserParameters {
     ) -> usize {
         let name = name.into();
         let ext = self.trafo.parameters_len();
-        let param = MinuitParameter::with_limits(ext, &name, value, error, lower, upper);
-        self.trafo.add(param);
-        self.name_map.insert(name, ext);
-        ext
+        self.push(
+            MinuitParameter::with_limits(ext, &name, value, error, lower, upper),
+            name,
+        )
     }
 
     /// Add a parameter with lower bound only.
@@ -69,10 +94,10 @@ impl MnUserParameters {
     ) -> usize {
         let name = name.into();
         let ext = self.trafo.parameters_len();
-        let param = MinuitParameter::with_lower_limit(ext, &name, value, error, lower);
-        self.trafo.add(param);
-        self.name_map.insert(name, ext);
-        ext
+        self.push(
+            MinuitParameter::with_lower_limit(ext, &name, value, error, lower),
+            name,
+        )
     }
 
     /// Add a parameter with upper bound only.
@@ -85,22 +110,94 @@ impl MnUserParameters {
     ) -> usize {
         let name = name.into();
         let ext = self.trafo.parameters_len();
-        let param = MinuitParameter::with_upper_limit(ext, &name, value, error, upper);
-        self.trafo.add(param);
-        self.name_map.insert(name, ext);
-        ext
+        self.push(
+            MinuitParameter::with_upper_limit(ext, &name, value, error, upper),
+            name,
+        )
     }
 
     /// Add a constant parameter (fixed, never released).
     pub fn add_const(&mut self, name: impl Into<String>, value: f64) -> usize {
         let name = name.into();
         let ext = self.trafo.parameters_len();
-        let param = MinuitParameter::constant(ext, &name, value);
-        self.trafo.add(param);
+        self.push(MinuitParameter::constant(ext, &name, value), name)
+    }
+
+    fn push(&mut self, param: MinuitParameter, name: String) -> usize {
+        self.check_new_name(&name);
+        let ext = self.trafo.add(param);
         self.name_map.insert(name, ext);
         ext
     }
 
+    fn check_new_name(&self, name: &str) {
+        if self.deny_duplicates && self.name_map.contains_key(name) {
+            panic!(
+                "{}",
+                DuplicateNameError {
+                    names: vec![name.to_string()],
+                }
+            );
+        }
+    }
+
+    /// Check that every parameter name is unique.
+    ///
+    /// Returns all names used more than once.
+    pub fn check_no_duplicate_names(&self) -> Result<(), DuplicateNameError> {
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        let mut names = Vec::new();
+        for p in self.trafo.parameters() {
+            let count = seen.entry(p.name()).or_insert(0);
+            *count += 1;
+            if *count == 2 {
+                names.push(p.name().to_string());
+            }
+        }
+        if names.is_empty() {
+            Ok(())
+        } else {
+            Err(DuplicateNameError { names })
+        }
+    }
+
+    /// Append the parameters of `other` after those of `self`.
+    ///
+    /// Values, errors, limits and fixed flags are kept; the parameters of
+    /// `other` get external indices offset by `self.len()`. Fails with every
+    /// overlapping name if the two collections share one.
+    pub fn merge(&self, other: &MnUserParameters) -> Result<MnUserParameters, DuplicateNameError> {
+        let names: Vec<String> = other
+            .params()
+            .iter()
+            .filter(|p| self.name_map.contains_key(p.name()))
+            .map(|p| p.name().to_string())
+            .collect();
+        if !names.is_empty() {
+            return Err(DuplicateNameError { names });
+        }
+
+        let mut joint = self.clone();
+        for p in other.params() {
+            let (name, value, error) = (p.name(), p.value(), p.error());
+            let ext = if p.is_const() {
+                joint.add_const(name, value)
+            } else if p.has_limits() {
+                joint.add_limited(name, value, error, p.lower_limit(), p.upper_limit())
+            } else if p.has_lower_limit() {
+                joint.add_lower_limited(name, value, error, p.lower_limit())
+            } else if p.has_upper_limit() {
+                joint.add_upper_limited(name, value, error, p.upper_limit())
+            } else {
+                joint.add(name, value, error)
+            };
+            if p.is_fixed() && !p.is_const() {
+                joint.fix(ext);
+            }
+        }
+        Ok(joint)
+    }
+
     /// Fix parameter by external index.
     pub fn fix(&mut self, ext: usize) {
         self.trafo.fix(ext);
@@ -147,6 +244,23 @@ impl MnUserParameters {
         self.name_map.insert(new, ext);
     }
 
+    /// Rename the parameter called `old`, keeping the name lookup in sync.
+    ///
+    /// Returns its external index, or `None` if no parameter is called `old`
+    /// or another parameter is already called `new` (strict mode panics
+    /// instead, as for `add`).
+    pub fn rename(&mut self, old: &str, new: &str) -> Option<usize> {
+        let ext = self.index(old)?;
+        if old != new {
+            self.check_new_name(new);
+            if self.name_map.contains_key(new) {
+                return None;
+            }
+            self.set_name(ext, new);
+        }
+        Some(ext)
+    }
+
     pub fn set_precision(&mut self, eps: f64) {
         self.trafo.precision_mut().set_precision(eps);
     }
@@ -194,6 +308,14 @@ impl MnUserParameters {
     pub fn params(&self) -> &[MinuitParameter] {
         self.trafo.parameters()
     }
+
+    /// Independent copy of the current parameters, limits, and fixed flags.
+    ///
+    /// Save one before changing limits or fixing parameters between fits;
+    /// later edits to `self` leave the snapshot untouched.
+    pub fn snapshot(&self) -> MnUserParameters {
+        self.clone()
+    }
 }
 
 impl Default for MnUserParameters {
@@ -248,6 +370,77 @@ mod tests {
         assert_eq!(p.index("alpha"), Some(0));
     }
 
+    #[test]
+    fn duplicate_names_are_reported() {
+        let mut p = MnUserParameters::new();
+        p.add("x", 1.0, 0.1);
+        p.add("y", 2.0, 0.2);
+        assert_eq!(p.check_no_duplicate_names(), Ok(()));
+
+        p.add("x", 3.0, 0.1);
+        p.add_const("y", 4.0);
+        p.add("x", 5.0, 0.1);
+        assert_eq!(
+            p.check_no_duplicate_names().unwrap_err().names,
+            vec!["x".to_string(), "y".to_string()]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate parameter names: x")]
+    fn strict_rejects_duplicate_add() {
+        let mut p = MnUserParameters::strict();
+        p.add("x", 1.0, 0.1);
+        p.add_limited("x", 1.0, 0.1, 0.0, 2.0);
+    }
+
+    #[test]
+    fn rename_updates_parameter_and_lookup() {
+        let mut p = MnUserParameters::strict();
+        p.add("x", 1.0, 0.1);
+        p.add("y", 2.0, 0.2);
+        assert_eq!(p.rename("x", "mu"), Some(0));
+        assert_eq!(p.index("mu"), Some(0));
+        assert_eq!(p.index("x"), None);
+        assert_eq!(p.params()[0].name(), "mu");
+        assert_eq!(p.rename("x", "nu"), None);
+    }
+
+    #[test]
+    fn rename_onto_existing_name_is_refused() {
+        let mut p = MnUserParameters::new();
+        p.add("x", 1.0, 0.1);
+        p.add("y", 2.0, 0.2);
+        assert_eq!(p.rename("x", "y"), None);
+        assert_eq!(p.index("x"), Some(0));
+        assert_eq!(p.index("y"), Some(1));
+        assert_eq!(p.params()[0].name(), "x");
+    }
+
+    #[test]
+    fn merge_appends_with_offset_indices() {
+        let mut a = MnUserParameters::new();
+        a.add("mu", 1.0, 0.1);
+        let mut b = MnUserParameters::new();
+        b.add_limited("sigma", 2.0, 0.2, 0.0, 5.0);
+        b.add("bkg", 3.0, 0.3);
+        b.fix(1);
+        b.add_const("lumi", 4.0);
+
+        let joint = a.merge(&b).unwrap();
+        assert_eq!(joint.len(), 4);
+        assert_eq!(joint.index("sigma"), Some(1));
+        assert_eq!(joint.index("lumi"), Some(3));
+        assert_eq!(joint.params()[1].number(), 1);
+        assert!(joint.parameter("sigma").unwrap().has_limits());
+        assert!(joint.parameter("bkg").unwrap().is_fixed());
+        assert!(joint.parameter("lumi").unwrap().is_const());
+        assert_eq!(joint.variable_parameters(), 2);
+
+        b.add("mu", 0.0, 0.1);
+        assert_eq!(a.merge(&b).unwrap_err().names, vec!["mu".to_string()]);
+    }
+
     #[test]
     fn set_lower_and_upper_limits() {
         let mut p = MnUserParameters::new();
@@ -261,6 +454,25 @@ mod tests {
         assert!((x.upper_limit() - 3.0).abs() < 1e-15);
     }
 
+    #[test]
+    fn snapshot_is_independent_of_original() {
+        let mut p = MnUserParameters::new();
+        p.add_limited("x", 1.0, 0.1, 0.0, 2.0);
+        p.add("y", 2.0, 0.2);
+        p.fix(1);
+        let snap = p.snapshot();
+
+        p.remove_limits(0);
+        p.release(1);
+        p.set_value(0, 5.0);
+
+        let x = snap.parameter("x").unwrap();
+        assert!(x.has_limits());
+        assert!((x.value() - 1.0).abs() < 1e-15);
+        assert!(snap.parameter("y").unwrap().is_fixed());
+        assert_eq!(snap.variable_parameters(), 1);
+    }
+
     #[test]
     fn set_precision_propagates_to_transformation() {
         let mut p = MnUserParameters::new();
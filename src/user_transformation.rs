@@ -105,6 +105,27 @@ impl MnUserTransformation {
         self.parameters[ext].set_value(val);
     }
 
+    /// Set every parameter value from a full external vector.
+    pub fn reset_values_from_external(&mut self, external: &[f64]) {
+        assert_eq!(external.len(), self.parameters.len());
+        for (ext, &val) in external.iter().enumerate() {
+            self.parameters[ext].set_value(val);
+        }
+    }
+
+    /// Set the variable parameter values from an internal vector.
+    ///
+    /// Bounded parameters are mapped through `int2ext`; fixed parameters
+    /// keep their current value.
+    pub fn reset_values_from_internal(&mut self, internal: &[f64]) {
+        assert_eq!(internal.len(), self.int_of_ext.len());
+        for (int, &int_val) in internal.iter().enumerate() {
+            let ext = self.int_of_ext[int];
+            let val = self.int2ext(ext, int_val);
+            self.parameters[ext].set_value(val);
+        }
+    }
+
     pub fn set_error(&mut self, ext: usize, err: f64) {
         self.parameters[ext].set_error(err);
     }
@@ -364,6 +385,24 @@ mod tests {
         assert!((back - 5.0).abs() < 1e-12);
     }
 
+    #[test]
+    fn reset_values_roundtrip() {
+        let params = vec![
+            MinuitParameter::with_limits(0, "x", 5.0, 0.1, 0.0, 10.0),
+            MinuitParameter::new(1, "y", 2.0, 0.2),
+        ];
+        let mut t = MnUserTransformation::new(params);
+        t.reset_values_from_external(&[7.5, -1.0]);
+        assert!((t.parameters()[0].value() - 7.5).abs() < 1e-15);
+        assert!((t.parameters()[1].value() + 1.0).abs() < 1e-15);
+
+        let internal = vec![t.ext2int(0, 2.5), 3.0];
+        t.reset_values_from_internal(&internal);
+        assert!((t.parameters()[0].value() - 2.5).abs() < 1e-12);
+        assert!((t.parameters()[1].value() - 3.0).abs() < 1e-15);
+        assert!((t.ext2int(0, t.parameters()[0].value()) - internal[0]).abs() < 1e-12);
+    }
+
     #[test]
     fn unbounded_passthrough() {
         let pi = std::f64::consts::PI;
@@ -133,3 +133,227 @@ fn contour_contains_same_points_as_points_call() {
     let contour = contours.contour(0, 1, 12);
     assert_eq!(points, contour.points);
 }
+
+/// Contour points of a correlated quadratic lie on the F = Fmin + Up level.
+#[test]
+fn contour_correlated_points_on_level() {
+    let correlated = |p: &[f64]| p[0] * p[0] + p[1] * p[1] + p[0] * p[1];
+
+    let result = MnMigrad::new()
+        .add("x", 5.0, 1.0)
+        .add("y", -3.0, 1.0)
+        .minimize(&correlated);
+    let hesse_result = MnHesse::new().calculate(&correlated, &result);
+
+    let points = MnContours::new(&correlated, &hesse_result).points(0, 1, 20);
+    assert_eq!(points.len(), 20);
+
+    let target = hesse_result.fval() + hesse_result.up();
+    for (x, y) in &points {
+        let f = correlated(&[*x, *y]);
+        assert!(
+            (f - target).abs() < 0.1 * hesse_result.up(),
+            "contour point ({x}, {y}) has f={f}, expected ~{target}"
+        );
+    }
+}
+
+/// With a third free parameter the contour is that of the profile, with
+/// the nuisance parameter re-minimized at every point.
+#[test]
+fn contour_profiles_other_parameters() {
+    // Profiling z out of (x - z)^2 + z^2 + y^2 leaves x^2 / 2 + y^2.
+    let fcn = |p: &[f64]| (p[0] - p[2]).powi(2) + p[2] * p[2] + p[1] * p[1];
+    let profile = |x: f64, y: f64| 0.5 * x * x + y * y;
+
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", -1.0, 1.0)
+        .add("z", 0.5, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+
+    let points = MnContours::new(&fcn, &hesse_result).points(0, 1, 12);
+    assert_eq!(points.len(), 12);
+
+    let target = hesse_result.fval() + hesse_result.up();
+    for (x, y) in &points {
+        let f = profile(*x, *y);
+        assert!(
+            (f - target).abs() < 0.15 * hesse_result.up(),
+            "contour point ({x}, {y}) has profile {f}, expected ~{target}"
+        );
+    }
+}
+
+/// A tighter tolerance puts the contour points closer to the iso-level.
+#[test]
+fn contour_tolerance_tightens_points() {
+    // Non-quadratic so the MINOS parabola steps do not land exactly.
+    let fcn = |p: &[f64]| p[0].powi(2) + p[1].powi(2) + p[0] * p[1] + 0.3 * p[0].powi(4);
+
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", -1.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+    let target = hesse_result.fval() + hesse_result.up();
+
+    let max_dev = |tol: f64| {
+        MnContours::new(&fcn, &hesse_result)
+            .with_tolerance(tol)
+            .points(0, 1, 16)
+            .iter()
+            .map(|(x, y)| (fcn(&[*x, *y]) - target).abs())
+            .fold(0.0, f64::max)
+    };
+
+    let coarse = max_dev(0.1);
+    let fine = max_dev(0.01);
+    assert!(
+        coarse < 0.1 * hesse_result.up(),
+        "coarse max deviation {coarse}"
+    );
+    assert!(fine < 0.5 * coarse, "fine {fine} vs coarse {coarse}");
+}
+
+/// `points_ordered` sweeps monotonically in angle and closes the polygon.
+#[test]
+fn contour_points_ordered_closed_sweep() {
+    let fcn = |p: &[f64]| p[0] * p[0] + p[1] * p[1] + 0.8 * p[0] * p[1];
+
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", -1.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+    let x_min = hesse_result.user_state().parameter(0).value();
+    let y_min = hesse_result.user_state().parameter(1).value();
+
+    let points = MnContours::new(&fcn, &hesse_result).points_ordered(0, 1, 10);
+    assert_eq!(points.len(), 11);
+    assert_eq!(points.first(), points.last());
+
+    let angles: Vec<f64> = points[..10]
+        .iter()
+        .map(|(x, y)| (y - y_min).atan2(x - x_min))
+        .collect();
+    assert!(
+        angles.windows(2).all(|w| w[0] <= w[1]),
+        "angles not monotonic: {angles:?}"
+    );
+}
+
+/// `points_sigma(.., 2.0, ..)` traces the Δχ² = 4 contour.
+#[test]
+fn contour_points_sigma_scales_up() {
+    let fcn = |p: &[f64]| p[0] * p[0] + 2.0 * p[1] * p[1];
+
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", -1.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+
+    let contours = MnContours::new(&fcn, &hesse_result);
+    let one = contours.points_sigma(0, 1, 1.0, 8);
+    let plain = contours.points(0, 1, 8);
+    assert_eq!(one, plain);
+
+    let two = contours.points_sigma(0, 1, 2.0, 8);
+    assert_eq!(two.len(), 8);
+    for (x, y) in &two {
+        let df = fcn(&[*x, *y]) - hesse_result.fval();
+        assert!((df - 4.0).abs() < 0.4, "Δf = {df} at ({x}, {y})");
+    }
+    // The minimum itself keeps Up = 1.
+    assert_eq!(hesse_result.up(), 1.0);
+}
+
+/// `with_up(2.3)` traces the Δχ² = 2.3 contour without touching the minimum.
+#[test]
+fn contour_with_up_override() {
+    let fcn = |p: &[f64]| p[0] * p[0] + 2.0 * p[1] * p[1];
+
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", -1.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+
+    let points = MnContours::new(&fcn, &hesse_result)
+        .with_up(2.3)
+        .points(0, 1, 8);
+    assert_eq!(points.len(), 8);
+    for (x, y) in &points {
+        let df = fcn(&[*x, *y]) - hesse_result.fval();
+        assert!((df - 2.3).abs() < 0.23, "Δf = {df} at ({x}, {y})");
+    }
+    assert_eq!(hesse_result.up(), 1.0);
+}
+
+/// Shoelace area and fitted ellipse match the analytic Δχ² = 1 ellipse.
+#[test]
+fn contour_area_and_ellipse_approx() {
+    let fcn = |p: &[f64]| p[0] * p[0] + p[1] * p[1] + 0.8 * p[0] * p[1];
+
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", -1.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+    let contours = MnContours::new(&fcn, &hesse_result);
+
+    // Hessian/2 = [[1, 0.4], [0.4, 1]] has eigenvalues 1.4 and 0.6, with the
+    // major axis along (1, -1).
+    let (major, minor, angle) = contours.ellipse_approx(0, 1);
+    assert!(
+        (major - 0.6f64.sqrt().recip()).abs() < 1e-2,
+        "major {major}"
+    );
+    assert!(
+        (minor - 1.4f64.sqrt().recip()).abs() < 1e-2,
+        "minor {minor}"
+    );
+    assert!(
+        (angle + std::f64::consts::FRAC_PI_4).abs() < 1e-2,
+        "angle {angle}"
+    );
+
+    let exact = std::f64::consts::PI * major * minor;
+    let area = contours.area(0, 1, 40);
+    assert!(
+        area < exact && area > 0.95 * exact,
+        "area {area} vs {exact}"
+    );
+}
+
+/// `refine` fills the gaps of a coarse contour with points on the level.
+#[test]
+fn contour_refine_fills_coarse_gaps() {
+    let correlated = |p: &[f64]| p[0] * p[0] + p[1] * p[1] + p[0] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 5.0, 1.0)
+        .add("y", -3.0, 1.0)
+        .minimize(&correlated);
+    let hesse_result = MnHesse::new().calculate(&correlated, &result);
+    let contours = MnContours::new(&correlated, &hesse_result);
+
+    let coarse = contours.points(0, 1, 4);
+    let refined = contours.refine(coarse.clone(), 0, 1, 0.05);
+    assert!(refined.len() > coarse.len());
+    // The original points are kept, in order.
+    let kept: Vec<_> = refined.iter().filter(|p| coarse.contains(p)).collect();
+    assert_eq!(kept, coarse.iter().collect::<Vec<_>>());
+
+    let target = hesse_result.fval() + hesse_result.up();
+    for (x, y) in &refined {
+        let f = correlated(&[*x, *y]);
+        assert!((f - target).abs() < 0.1 * hesse_result.up());
+    }
+
+    // A closed loop stays closed, and a loose accuracy adds nothing.
+    let ordered = contours.points_ordered(0, 1, 8);
+    let loose = contours.refine(ordered.clone(), 0, 1, 10.0);
+    assert_eq!(loose, ordered);
+}
@@ -1,4 +1,4 @@
-use minuit2::{MnHesse, MnMigrad};
+use minuit2::{MnHesse, MnMigrad, MnUserCovariance};
 
 /// Quadratic: f(x,y) = a*x^2 + b*y^2
 /// ROOT Minuit2 user covariance convention: V = 2 * up * H^-1.
@@ -179,3 +179,562 @@ fn hesse_flat_parameter_returns_failed_state_without_covariance() {
         "failed Hesse state must not expose covariance"
     );
 }
+
+/// Diagonal-only Hesse reproduces the full errors on a separable function
+/// with fewer calls, and flags the matrix as approximate.
+#[test]
+fn hesse_diagonal_only_matches_full_on_separable_function() {
+    use minuit2::minimum::error::ErrorMatrixStatus;
+
+    let fcn = |p: &[f64]| {
+        (p[0] - 1.0).powi(2) + 4.0 * (p[1] + 2.0).powi(2) + 0.5 * p[2].powi(2) + p[3].powi(4)
+    };
+    let result = MnMigrad::new()
+        .add("a", 3.0, 1.0)
+        .add("b", 1.0, 1.0)
+        .add("c", -2.0, 1.0)
+        .add("d", 0.8, 0.5)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let full = MnHesse::new().calculate(&fcn, &result);
+    let diag = MnHesse::new().diagonal_only().calculate(&fcn, &result);
+
+    assert_eq!(
+        diag.state().error().status(),
+        ErrorMatrixStatus::DiagonalOnly
+    );
+    assert_eq!(full.state().error().status(), ErrorMatrixStatus::Accurate);
+    assert_eq!(diag.error_matrix_status(), ErrorMatrixStatus::DiagonalOnly);
+    assert_eq!(full.error_matrix_status(), ErrorMatrixStatus::Accurate);
+    assert!(
+        diag.nfcn() < full.nfcn(),
+        "diagonal-only should skip the cross-derivative calls"
+    );
+
+    for name in ["a", "b", "c"] {
+        let e_full = full.user_state().error(name).unwrap();
+        let e_diag = diag.user_state().error(name).unwrap();
+        assert!(
+            (e_full - e_diag).abs() < 1e-6 * e_full,
+            "{name}: full {e_full} vs diagonal {e_diag}"
+        );
+    }
+    let cov = diag.user_state().covariance().unwrap();
+    for i in 0..4 {
+        for j in 0..4 {
+            if i != j {
+                assert_eq!(cov.get(i, j), 0.0);
+            }
+        }
+    }
+}
+
+/// Preset constructors match the explicit strategy levels.
+#[test]
+fn hesse_strategy_presets() {
+    use minuit2::MnStrategy;
+
+    for (preset, level) in [
+        (MnHesse::low(), 0),
+        (MnHesse::medium(), 1),
+        (MnHesse::high(), 2),
+    ] {
+        let explicit = MnHesse::new().with_strategy(level);
+        assert_eq!(preset.ncycles(), explicit.ncycles());
+        assert_eq!(preset.tolerstp(), explicit.tolerstp());
+        assert_eq!(preset.toler_g2(), explicit.toler_g2());
+    }
+
+    let small = MnHesse::for_ndim(3);
+    let large = MnHesse::for_ndim(100);
+    assert_eq!(small.ncycles(), MnStrategy::for_ndim(3).hessian_ncycles());
+    assert_eq!(large.ncycles(), MnStrategy::for_ndim(100).hessian_ncycles());
+}
+
+/// `update_from_hesse` takes the Hesse covariance but keeps the Migrad history.
+#[test]
+fn update_from_hesse_keeps_original_history() {
+    let fcn = |p: &[f64]| 2.0 * p[0] * p[0] + 8.0 * p[1] * p[1] + p[0] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 5.0, 1.0)
+        .add("y", -3.0, 1.0)
+        .minimize(&fcn);
+    let hesse = MnHesse::new().calculate(&fcn, &result);
+    let merged = result.update_from_hesse(&hesse);
+
+    assert_eq!(merged.nfcn(), result.nfcn());
+    assert_eq!(merged.edm(), result.edm());
+    assert_eq!(merged.states().len(), result.states().len());
+    assert_eq!(merged.is_valid(), result.is_valid());
+
+    let cov = merged.user_state().covariance().unwrap();
+    let hesse_cov = hesse.user_state().covariance().unwrap();
+    for i in 0..2 {
+        for j in 0..2 {
+            assert_eq!(cov.get(i, j), hesse_cov.get(i, j));
+        }
+    }
+    assert_eq!(
+        merged.user_state().error("x"),
+        hesse.user_state().error("x")
+    );
+}
+
+/// `global_cc_map` keys the global correlations by parameter name.
+#[test]
+fn hesse_global_cc_map_by_name() {
+    let fcn = |p: &[f64]| p[0] * p[0] + p[1] * p[1] + p[0] * p[1] + p[2] * p[2];
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", 2.0, 1.0)
+        .add("z", 3.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+
+    let map = hesse_result.global_cc_map().expect("covariance available");
+    let gcc = hesse_result.user_state().global_cc().unwrap();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["x"], gcc[0]);
+    assert_eq!(map["z"], gcc[2]);
+    assert!(map["y"] > 0.1);
+    assert!(map["z"].abs() < 1e-6);
+}
+
+/// An FCN with an analytical Hessian skips the finite-difference steps.
+#[test]
+fn hesse_uses_analytical_hessian() {
+    use minuit2::FCN;
+    use std::cell::Cell;
+
+    // f = x^2 + 4 y^2 + x y, Hessian [[2, 1], [1, 8]].
+    struct WithHessian {
+        calls: Cell<usize>,
+    }
+    impl FCN for WithHessian {
+        fn value(&self, p: &[f64]) -> f64 {
+            self.calls.set(self.calls.get() + 1);
+            p[0] * p[0] + 4.0 * p[1] * p[1] + p[0] * p[1]
+        }
+        fn has_hessian(&self) -> bool {
+            true
+        }
+        fn hessian(&self, _p: &[f64]) -> Vec<f64> {
+            vec![2.0, 1.0, 8.0]
+        }
+    }
+
+    let fcn = WithHessian {
+        calls: Cell::new(0),
+    };
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let before = fcn.calls.get();
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+    assert_eq!(fcn.calls.get(), before, "no FCN calls expected");
+
+    // V = 2 * up * H^-1, H^-1 = [[8, -1], [-1, 2]] / 15.
+    let cov = hesse_result.user_state().covariance().unwrap();
+    assert!((cov.get(0, 0) - 16.0 / 15.0).abs() < 1e-10);
+    assert!((cov.get(0, 1) + 2.0 / 15.0).abs() < 1e-10);
+    assert!((cov.get(1, 1) - 4.0 / 15.0).abs() < 1e-10);
+
+    // An explicit diagonal-only request drops the analytical off-diagonal
+    // terms too.
+    let diag = MnHesse::new().diagonal_only().calculate(&fcn, &result);
+    assert_eq!(
+        diag.error_matrix_status(),
+        minuit2::ErrorMatrixStatus::DiagonalOnly
+    );
+    let cov = diag.user_state().covariance().unwrap();
+    assert!((cov.get(0, 0) - 1.0).abs() < 1e-10);
+    assert_eq!(cov.get(0, 1), 0.0);
+    assert!((cov.get(1, 1) - 0.25).abs() < 1e-10);
+
+    // The low strategy only saves cross-derivative calls, and an analytical
+    // Hessian makes none, so it keeps the full matrix.
+    let low = MnHesse::low().calculate(&fcn, &result);
+    assert_eq!(
+        low.error_matrix_status(),
+        minuit2::ErrorMatrixStatus::Accurate
+    );
+    let cov = low.user_state().covariance().unwrap();
+    assert!((cov.get(0, 1) + 2.0 / 15.0).abs() < 1e-10);
+}
+
+/// With a limited parameter the analytical Hessian is not used, so Hesse
+/// agrees with the numerical result instead of dropping the transform's
+/// curvature.
+#[test]
+fn hesse_analytical_hessian_with_limits_matches_numerical() {
+    use minuit2::FCN;
+
+    // Unconstrained minimum at x = -0.1, just outside the limit x >= 0.
+    struct WithHessian {
+        provide_hessian: bool,
+    }
+    impl FCN for WithHessian {
+        fn value(&self, p: &[f64]) -> f64 {
+            (p[0] + 0.1).powi(2) + 4.0 * p[1] * p[1] + 0.5 * p[0] * p[1]
+        }
+        fn has_hessian(&self) -> bool {
+            self.provide_hessian
+        }
+        fn hessian(&self, _p: &[f64]) -> Vec<f64> {
+            vec![2.0, 0.5, 8.0]
+        }
+    }
+
+    let errors = |provide_hessian: bool| {
+        let fcn = WithHessian { provide_hessian };
+        let result = MnMigrad::new()
+            .add_lower_limited("x", 1.0, 0.5, 0.0)
+            .add("y", 1.0, 1.0)
+            .minimize(&fcn);
+        assert!(result.params()[0] < 1e-2);
+        let hesse_result = MnHesse::new().calculate(&fcn, &result);
+        let state = hesse_result.user_state();
+        (state.error("x").unwrap(), state.error("y").unwrap())
+    };
+
+    let (x_analytical, y_analytical) = errors(true);
+    let (x_numerical, y_numerical) = errors(false);
+    assert!(x_analytical.is_finite());
+    assert!((x_analytical - x_numerical).abs() < 1e-6 * x_numerical.max(1.0));
+    assert!((y_analytical - y_numerical).abs() < 1e-6);
+}
+
+/// Soft bounds keep the analytical-Hessian path and add the penalty curvature.
+#[test]
+fn hesse_soft_bounded_analytical_hessian() {
+    use minuit2::FCN;
+    use minuit2::SoftBoundedFCN;
+    use minuit2::soft_bound::SoftBound;
+    use std::cell::Cell;
+
+    // f = (x - 3)^2 + y^2, Hessian [[2, 0], [0, 2]].
+    struct WithHessian {
+        calls: Cell<usize>,
+    }
+    impl FCN for WithHessian {
+        fn value(&self, p: &[f64]) -> f64 {
+            self.calls.set(self.calls.get() + 1);
+            (p[0] - 3.0).powi(2) + p[1] * p[1]
+        }
+        fn has_hessian(&self) -> bool {
+            true
+        }
+        fn hessian(&self, _p: &[f64]) -> Vec<f64> {
+            vec![2.0, 0.0, 2.0]
+        }
+    }
+
+    let fcn = WithHessian {
+        calls: Cell::new(0),
+    };
+    let result = MnMigrad::new()
+        .add_soft_bounded("x", 1.0, 1.0, 0.0, 2.0, 100.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+    assert!(result.params()[0] > 2.0);
+
+    let bounds = [SoftBound {
+        par: 0,
+        lower: 0.0,
+        upper: 2.0,
+        strength: 100.0,
+    }];
+    let bounded = SoftBoundedFCN::new(&fcn, &bounds);
+    assert!(bounded.has_hessian());
+
+    let before = fcn.calls.get();
+    let hesse_result = MnHesse::new().calculate(&bounded, &result);
+    assert_eq!(fcn.calls.get(), before, "no FCN calls expected");
+
+    // Outside the bound the penalty adds 2 * strength to H(x, x).
+    let cov = hesse_result.user_state().covariance().unwrap();
+    assert!((cov.get(0, 0) - 2.0 / 202.0).abs() < 1e-10);
+    assert!((cov.get(1, 1) - 1.0).abs() < 1e-10);
+}
+
+/// An FCN-provided `g2()` replaces the diagonal finite differences.
+#[test]
+fn hesse_uses_analytical_g2() {
+    use minuit2::FCN;
+    use std::cell::Cell;
+
+    // f = x^2 + 4 y^2 + x y, diagonal second derivatives [2, 8].
+    struct WithG2 {
+        calls: Cell<usize>,
+        provide_g2: bool,
+    }
+    impl FCN for WithG2 {
+        fn value(&self, p: &[f64]) -> f64 {
+            self.calls.set(self.calls.get() + 1);
+            p[0] * p[0] + 4.0 * p[1] * p[1] + p[0] * p[1]
+        }
+        fn has_g2(&self) -> bool {
+            self.provide_g2
+        }
+        fn g2(&self, _p: &[f64]) -> Vec<f64> {
+            vec![2.0, 8.0]
+        }
+    }
+
+    let hesse_calls = |provide_g2: bool| {
+        let fcn = WithG2 {
+            calls: Cell::new(0),
+            provide_g2,
+        };
+        let result = MnMigrad::new()
+            .add("x", 1.0, 1.0)
+            .add("y", 1.0, 1.0)
+            .minimize(&fcn);
+        assert!(result.is_valid());
+
+        let before = fcn.calls.get();
+        let hesse_result = MnHesse::new().calculate(&fcn, &result);
+        let cov = hesse_result.user_state().covariance().unwrap();
+        assert!((cov.get(0, 0) - 16.0 / 15.0).abs() < 1e-4);
+        assert!((cov.get(0, 1) + 2.0 / 15.0).abs() < 1e-4);
+        assert!((cov.get(1, 1) - 4.0 / 15.0).abs() < 1e-4);
+        fcn.calls.get() - before
+    };
+
+    assert!(hesse_calls(true) < hesse_calls(false));
+}
+
+/// An FCN-provided `g2()` for a parameter pinned near its limit matches the
+/// numerical Hesse; the transform's curvature is not dropped.
+#[test]
+fn hesse_analytical_g2_near_limit_matches_numerical() {
+    use minuit2::FCN;
+
+    // Unconstrained minimum at x = -0.1, just outside the limit x >= 0, so
+    // the fit ends at the bound with a non-zero external gradient.
+    struct WithG2 {
+        provide_g2: bool,
+    }
+    impl FCN for WithG2 {
+        fn value(&self, p: &[f64]) -> f64 {
+            (p[0] + 0.1).powi(2) + 4.0 * p[1] * p[1]
+        }
+        fn has_g2(&self) -> bool {
+            self.provide_g2
+        }
+        fn g2(&self, _p: &[f64]) -> Vec<f64> {
+            vec![2.0, 8.0]
+        }
+    }
+
+    let errors = |provide_g2: bool| {
+        let fcn = WithG2 { provide_g2 };
+        let result = MnMigrad::new()
+            .add_limited("x", 1.0, 0.5, 0.0, 5.0)
+            .add("y", 1.0, 1.0)
+            .minimize(&fcn);
+        assert!(result.params()[0] < 1e-2);
+        let hesse_result = MnHesse::new().calculate(&fcn, &result);
+        let state = hesse_result.user_state();
+        (state.error("x").unwrap(), state.error("y").unwrap())
+    };
+
+    let (x_analytical, y_analytical) = errors(true);
+    let (x_numerical, y_numerical) = errors(false);
+    assert!(x_analytical.is_finite());
+    assert!((x_analytical - x_numerical).abs() < 1e-6 * x_numerical.max(1.0));
+    assert!((y_analytical - y_numerical).abs() < 1e-4);
+}
+
+/// `last_ncalls` reports the evaluations of the latest Hesse run only.
+#[test]
+fn hesse_last_ncalls_counts_evaluations() {
+    use std::cell::Cell;
+
+    let calls = Cell::new(0usize);
+    let fcn = |p: &[f64]| {
+        calls.set(calls.get() + 1);
+        p[0] * p[0] + 3.0 * p[1] * p[1] + p[0] * p[1]
+    };
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+
+    let hesse = MnHesse::new();
+    assert_eq!(hesse.last_ncalls(), 0);
+
+    let before = calls.get();
+    hesse.calculate(&fcn, &result);
+    assert_eq!(hesse.last_ncalls(), calls.get() - before);
+    assert!(hesse.last_ncalls() > 0);
+
+    let before = calls.get();
+    hesse.calculate_errors(&fcn, &result);
+    assert_eq!(hesse.last_ncalls(), calls.get() - before);
+
+    // The call counter must not stop a configured MnHesse being shared.
+    fn assert_sync<T: Sync>(_: &T) {}
+    assert_sync(&hesse);
+}
+
+/// The low strategy skips cross derivatives, so correlations are dropped.
+#[test]
+fn hesse_low_strategy_is_diagonal() {
+    use minuit2::ErrorMatrixStatus;
+
+    let fcn = |p: &[f64]| p[0] * p[0] + 2.0 * p[1] * p[1] + p[0] * p[1] + p[2] * p[2];
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .add("z", 1.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let low = MnHesse::low().calculate(&fcn, &result);
+    let explicit = MnHesse::new().diagonal_only().calculate(&fcn, &result);
+    assert_eq!(low.error_matrix_status(), ErrorMatrixStatus::DiagonalOnly);
+    let cov = low.user_state().covariance().unwrap();
+    assert_eq!(cov.get(0, 1), 0.0);
+    assert!(cov.get(0, 0) > 0.0);
+
+    let full = MnHesse::medium().calculate(&fcn, &result);
+    assert_eq!(full.error_matrix_status(), ErrorMatrixStatus::Accurate);
+    assert!(full.user_state().covariance().unwrap().get(0, 1).abs() > 0.1);
+    assert_eq!(
+        explicit.error_matrix_status(),
+        ErrorMatrixStatus::DiagonalOnly
+    );
+}
+
+/// A cross-derivative budget truncates Step 3 and flags the matrix approximate.
+#[test]
+fn hesse_max_cross_calls_truncates_off_diagonal() {
+    use minuit2::minimum::ErrorMatrixStatus;
+
+    let fcn = |p: &[f64]| p[0] * p[0] + p[1] * p[1] + p[2] * p[2] + 0.5 * p[0] * p[1];
+    let result = MnMigrad::new()
+        .add("a", 1.0, 1.0)
+        .add("b", 1.0, 1.0)
+        .add("c", 1.0, 1.0)
+        .minimize(&fcn);
+
+    let full = MnHesse::new().calculate(&fcn, &result);
+    let hesse = MnHesse::new().with_max_cross_calls(1);
+    let capped = hesse.calculate(&fcn, &result);
+    assert_eq!(full.error_matrix_status(), ErrorMatrixStatus::Accurate);
+    assert_eq!(
+        capped.error_matrix_status(),
+        ErrorMatrixStatus::CrossTermsTruncated
+    );
+
+    // Only the (a, b) element was computed; the rest stayed zero.
+    let cov = capped.user_state().covariance().unwrap();
+    assert!(cov.get(0, 1).abs() > 1e-3);
+    assert_eq!(cov.get(0, 2), 0.0);
+    assert_eq!(cov.get(1, 2), 0.0);
+
+    // A budget covering all n(n-1)/2 elements changes nothing.
+    let ample = MnHesse::new()
+        .with_max_cross_calls(3)
+        .calculate(&fcn, &result);
+    assert_eq!(ample.error_matrix_status(), ErrorMatrixStatus::Accurate);
+}
+
+/// A Gaussian prior adds its inverse covariance to the Hessian.
+#[test]
+fn hesse_with_prior_combines_information() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] - 2.0).powi(2);
+    let result = MnMigrad::new()
+        .add("x", 0.0, 1.0)
+        .add("y", 0.0, 1.0)
+        .minimize(&fcn);
+
+    // Likelihood covariance is the identity; the prior is diag(1, 3).
+    let prior = MnUserCovariance::from_vec(vec![1.0, 0.0, 3.0], 2);
+    let posterior = MnHesse::new()
+        .calculate_with_prior(&fcn, &result, &prior)
+        .unwrap();
+    let cov = posterior.user_state().covariance().unwrap();
+    assert!((cov.get(0, 0) - 0.5).abs() < 1e-6);
+    assert!((cov.get(1, 1) - 0.75).abs() < 1e-6);
+    assert!(cov.get(0, 1).abs() < 1e-6);
+    assert!((posterior.user_state().error("x").unwrap() - 0.5f64.sqrt()).abs() < 1e-6);
+}
+
+/// A prior that cannot be combined is reported instead of ignored.
+#[test]
+fn hesse_with_prior_rejects_bad_priors() {
+    use minuit2::PriorError;
+
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] - 2.0).powi(2);
+    let result = MnMigrad::new()
+        .add("x", 0.0, 1.0)
+        .add("y", 0.0, 1.0)
+        .minimize(&fcn);
+
+    let wrong = MnUserCovariance::from_vec(vec![1.0], 1);
+    assert_eq!(
+        MnHesse::new()
+            .calculate_with_prior(&fcn, &result, &wrong)
+            .err(),
+        Some(PriorError::DimensionMismatch {
+            expected: 2,
+            found: 1
+        })
+    );
+
+    let singular = MnUserCovariance::from_vec(vec![1.0, 1.0, 1.0], 2);
+    assert_eq!(
+        MnHesse::new()
+            .calculate_with_prior(&fcn, &result, &singular)
+            .err(),
+        Some(PriorError::SingularPrior)
+    );
+}
+
+/// Covariance guards distinguish full and diagonal-only Hesse.
+#[test]
+fn covariance_accuracy_guards() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2) + 0.5 * p[0] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 0.0, 1.0)
+        .add("y", 0.0, 1.0)
+        .minimize(&fcn);
+
+    let full = MnHesse::new().calculate(&fcn, &result);
+    assert!(full.has_accurate_covariance());
+    assert!(full.has_approximate_covariance());
+
+    let diag = MnHesse::new().diagonal_only().calculate(&fcn, &result);
+    assert!(!diag.has_accurate_covariance());
+    assert!(diag.has_approximate_covariance());
+}
+
+/// `errors_as_map` keys Hesse errors by name and skips fixed parameters.
+#[test]
+fn hesse_errors_as_map() {
+    let fcn = |p: &[f64]| 2.0 * (p[0] - 1.0).powi(2) + 8.0 * p[1] * p[1] + p[2];
+    let result = MnMigrad::new()
+        .add("x", 0.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .add("z", 3.0, 1.0)
+        .fix(2)
+        .add_const("c", 4.0)
+        .minimize(&fcn);
+
+    let map = MnHesse::new().errors_as_map(&fcn, &result);
+    assert_eq!(map.len(), 2);
+    let (x, ex) = map["x"];
+    let (y, ey) = map["y"];
+    assert!((x - 1.0).abs() < 1e-4 && y.abs() < 1e-4);
+    assert!((ex - 0.5f64.sqrt()).abs() < 1e-4);
+    assert!((ey - 0.125f64.sqrt()).abs() < 1e-4);
+    assert!(!map.contains_key("z"));
+    assert!(!map.contains_key("c"));
+}
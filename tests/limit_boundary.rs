@@ -44,3 +44,38 @@ fn doubly_limited_started_at_lower_bound_escapes() {
         r.params()[0]
     );
 }
+
+/// `any_at_boundary` flags a fit that ends on its limit.
+#[test]
+fn any_at_boundary_flags_pinned_fit() {
+    let pinned = MnMigrad::new()
+        .add_limited("x", 2.0, 0.5, 0.0, 5.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&|p: &[f64]| (p[0] + 1.0).powi(2) + p[1] * p[1]);
+    assert!(pinned.any_at_boundary(1.0));
+
+    let interior = MnMigrad::new()
+        .add_limited("x", 2.0, 0.5, 0.0, 5.0)
+        .minimize(&|p: &[f64]| 100.0 * (p[0] - 2.5).powi(2));
+    assert!(!interior.any_at_boundary(1.0));
+}
+
+/// `params_at_limit` lists exactly the free parameters pinned at a limit.
+#[test]
+fn params_at_limit_lists_pinned_indices() {
+    let fcn = |p: &[f64]| (p[0] + 1.0).powi(2) + p[1] * p[1] + (p[2] - 6.0).powi(2);
+    let result = MnMigrad::new()
+        .add_limited("x", 2.0, 0.5, 0.0, 5.0)
+        .add("y", 1.0, 1.0)
+        .add_limited("z", 2.0, 0.5, 0.0, 5.0)
+        .minimize(&fcn);
+
+    assert!(result.is_at_any_limit());
+    assert_eq!(result.params_at_limit(), vec![0, 2]);
+
+    let interior = MnMigrad::new()
+        .add_limited("x", 2.0, 0.5, 0.0, 5.0)
+        .minimize(&|p: &[f64]| 100.0 * (p[0] - 2.5).powi(2));
+    assert!(!interior.is_at_any_limit());
+    assert!(interior.params_at_limit().is_empty());
+}
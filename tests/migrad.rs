@@ -2,7 +2,8 @@ use nalgebra::DVector;
 mod common;
 
 use minuit2::{
-    FCN, FunctionMinimum, MinuitParameter, MnMigrad, MnSimplex, MnUserTransformation,
+    FCN, FCNResiduals, FunctionMinimum, GradientMethod, MinimizationError, MinuitParameter,
+    MnMigrad, MnSimplex, MnStrategy, MnUserTransformation, ParameterError, StrategyValidationError,
     minimum::{parameters::MinimumParameters, seed::MinimumSeed, state::MinimumState},
 };
 
@@ -295,3 +296,537 @@ fn display_output_marks_above_max_edm_warning() {
     assert!(output.contains("WARNING: EDM above maximum"));
     assert!(output.contains("valid:     false"));
 }
+
+/// `max_iterations` stops the DFP loop before the call budget is exhausted.
+#[test]
+fn max_iterations_limits_dfp_loop() {
+    let rosenbrock = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+
+    let full = MnMigrad::new()
+        .add("x", -1.0, 1.0)
+        .add("y", -1.0, 1.0)
+        .minimize(&rosenbrock);
+    let capped = MnMigrad::new()
+        .add("x", -1.0, 1.0)
+        .add("y", -1.0, 1.0)
+        .max_iterations(2)
+        .minimize(&rosenbrock);
+
+    assert!(full.is_valid());
+    assert!(
+        !capped.is_valid(),
+        "two iterations cannot reach Rosenbrock's minimum"
+    );
+    assert!(!capped.reached_call_limit());
+    // Two DFP states plus at most one Hesse verification state.
+    assert!(
+        capped.states().len() <= 3,
+        "got {} states",
+        capped.states().len()
+    );
+    assert!(capped.nfcn() < full.nfcn());
+}
+
+/// `is_converged` rejects fits pinned to a limit; `convergence_score` is EDM
+/// relative to the Migrad target.
+#[test]
+fn is_converged_and_convergence_score() {
+    let free = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add("y", -2.0, 1.0)
+        .minimize(&|p: &[f64]| p[0] * p[0] + p[1] * p[1]);
+    assert!(free.is_converged());
+    let score = free.convergence_score(0.1);
+    assert!((score - free.edm() / (0.002 * 0.1 * free.up())).abs() < 1e-12);
+    assert!(score < 1.0, "score={score}");
+
+    // True minimum at x = -1 lies outside [0, 5]; the fit ends on the limit.
+    let pinned = MnMigrad::new()
+        .add_limited("x", 2.0, 0.5, 0.0, 5.0)
+        .minimize(&|p: &[f64]| (p[0] + 1.0).powi(2));
+    assert!(!pinned.is_converged());
+}
+
+/// `recompute_edm` evaluates `0.5 * g^T V g` on the final state and stores it.
+#[test]
+fn recompute_edm_updates_final_state() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + 4.0 * (p[1] + 2.0).powi(2) + p[0] * p[1];
+    let mut result = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let state = result.state();
+    let g = state.gradient().grad();
+    let expected = 0.5 * g.dot(&(state.error().matrix() * g));
+
+    result.set_error_def(0.5);
+    let edm = result.recompute_edm();
+    assert!((edm - expected).abs() <= 1e-15 * expected.abs().max(1.0));
+    assert_eq!(result.edm(), edm);
+    assert_eq!(result.user_state().edm(), edm);
+    assert!(edm < 0.002 * 0.1 * result.up());
+}
+
+/// `fval_improvement` measures progress from the seed's function value.
+#[test]
+fn fval_improvement_and_iterations() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2);
+    let result = MnMigrad::new()
+        .add("x", 4.0, 1.0)
+        .add("y", 2.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    assert!((result.seed().fval() - fcn(&[4.0, 2.0])).abs() < 1e-12);
+    assert!((result.fval_improvement() - (25.0 - result.fval())).abs() < 1e-12);
+    assert_eq!(result.n_iterations(), result.states().len());
+    assert!(result.n_iterations() >= 1);
+}
+
+/// `add_params` / `add_limited_params` match the equivalent `.add` chains.
+#[test]
+fn add_params_from_slices() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2);
+
+    let chained = MnMigrad::new()
+        .add("x", 0.0, 0.5)
+        .add("y", 0.0, 0.5)
+        .minimize(&fcn);
+    let sliced = MnMigrad::new()
+        .add_params(&["x", "y"], &[0.0, 0.0], &[0.5, 0.5])
+        .unwrap()
+        .minimize(&fcn);
+    assert_eq!(chained.params(), sliced.params());
+    assert_eq!(chained.nfcn(), sliced.nfcn());
+
+    let limited = MnMigrad::new()
+        .add_limited_params(
+            &["x", "y"],
+            &[0.5, -1.0],
+            &[0.1, 0.1],
+            &[0.0, -5.0],
+            &[2.0, 0.0],
+        )
+        .unwrap()
+        .minimize(&fcn);
+    assert!(limited.is_valid());
+    assert!(limited.user_state().parameter(1).has_limits());
+
+    assert_eq!(
+        MnMigrad::new()
+            .add_params(&["x", "y"], &[0.0], &[0.5, 0.5])
+            .err(),
+        Some(ParameterError::LengthMismatch)
+    );
+    assert_eq!(
+        MnMigrad::new()
+            .add_limited_params(&["x"], &[0.0], &[0.5], &[-1.0], &[])
+            .err(),
+        Some(ParameterError::LengthMismatch)
+    );
+}
+
+/// `add_indexed` names parameters `prefix_0`, `prefix_1`, ...
+#[test]
+fn add_indexed_generates_names() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2) + p[2] * p[2];
+    let result = MnMigrad::new()
+        .add_indexed("c", &[0.0, 0.0], &[0.5, 0.5])
+        .unwrap()
+        .add_indexed_limited("b", &[0.5], &[0.1], &[-1.0], &[1.0])
+        .unwrap()
+        .minimize(&fcn);
+    assert!(result.is_valid());
+    let names: Vec<String> = result
+        .named_params_with_errors()
+        .into_iter()
+        .map(|(name, _, _)| name)
+        .collect();
+    assert_eq!(names, ["c_0", "c_1", "b_0"]);
+    assert!(result.user_state().parameter(2).has_limits());
+
+    assert_eq!(
+        MnMigrad::new().add_indexed("c", &[0.0, 1.0], &[0.5]).err(),
+        Some(ParameterError::LengthMismatch)
+    );
+}
+
+/// A soft bound pulls the fit back towards the allowed range without a
+/// parameter transform.
+#[test]
+fn soft_bounded_parameter() {
+    // Unconstrained minimum at x = 3, soft bound [0, 2].
+    let fcn = |p: &[f64]| (p[0] - 3.0).powi(2) + (p[1] - 1.0).powi(2);
+    let result = MnMigrad::new()
+        .add_soft_bounded("x", 1.0, 0.5, 0.0, 2.0, 100.0)
+        .add("y", 0.0, 0.5)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    // Stationary point of (x-3)^2 + 100 (x-2)^2: x = 203 / 101.
+    let x = result.params()[0];
+    assert!((x - 203.0 / 101.0).abs() < 1e-3, "x = {x}");
+    assert!(!result.user_state().parameter(0).has_lower_limit());
+    // Reported fval includes the penalty.
+    let expected = fcn(&result.params()) + 100.0 * (x - 2.0).powi(2);
+    assert!((result.fval() - expected).abs() < 1e-12);
+}
+
+/// A custom strategy is validated when it is set, not when minimizing.
+#[test]
+fn custom_strategy_is_validated_by_setter() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2);
+    let mut strategy = MnStrategy::new(2);
+    strategy.set_gradient_ncycles(5);
+    let result = MnMigrad::new()
+        .add("x", 0.0, 1.0)
+        .with_custom_strategy(strategy)
+        .unwrap()
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    strategy.set_gradient_ncycles(0);
+    assert_eq!(
+        MnMigrad::new().with_custom_strategy(strategy).err(),
+        Some(vec![StrategyValidationError::GradNcycles])
+    );
+}
+
+/// `minimize_strict` maps each failure flag to a distinct error.
+#[test]
+fn minimize_strict_classifies_failures() {
+    let rosen = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+    let migrad = MnMigrad::new().add("x", -1.2, 0.1).add("y", 1.0, 0.1);
+
+    let ok = migrad.minimize_strict(&rosen).expect("should converge");
+    assert!(ok.is_valid());
+
+    match migrad.clone().max_fcn(10).minimize_strict(&rosen) {
+        Err(MinimizationError::CallLimit { nfcn }) => assert!(nfcn >= 10),
+        other => panic!("expected CallLimit, got {other:?}"),
+    }
+
+    match migrad.clone().max_iterations(1).minimize_strict(&rosen) {
+        Err(MinimizationError::AboveMaxEdm { edm }) => assert!(edm > 0.0),
+        other => panic!("expected AboveMaxEdm, got {other:?}"),
+    }
+}
+
+/// Hessian damping: zero reproduces plain DFP, and 0.2 changes the path but
+/// still reaches the Rosenbrock minimum from a distant start.
+#[test]
+fn hessian_damping_rosenbrock() {
+    let rosen = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+    let migrad = MnMigrad::new().add("x", -5.0, 0.1).add("y", 20.0, 0.1);
+
+    let plain = migrad.minimize(&rosen);
+    let zero = migrad.clone().with_hessian_damping(0.0).minimize(&rosen);
+    assert_eq!(plain.params(), zero.params());
+    assert_eq!(plain.nfcn(), zero.nfcn());
+
+    let damped = migrad.clone().with_hessian_damping(0.2).minimize(&rosen);
+    assert!(damped.is_valid(), "damped fit should converge");
+    let p = damped.params();
+    assert!((p[0] - 1.0).abs() < 1e-2, "x = {}", p[0]);
+    assert!((p[1] - 1.0).abs() < 2e-2, "y = {}", p[1]);
+    assert_ne!(
+        damped.nfcn(),
+        plain.nfcn(),
+        "damping should change the path"
+    );
+}
+
+/// `with_precision` reaches the transformation used by the fit.
+#[test]
+fn with_precision_overrides_machine_precision() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2);
+    let result = MnMigrad::new()
+        .with_precision(1.0e-12)
+        .add("x", 0.0, 0.5)
+        .add("y", 0.0, 0.5)
+        .minimize(&fcn);
+
+    assert!(result.is_valid());
+    assert_eq!(result.seed().trafo().precision().eps(), 1.0e-12);
+    assert!((result.params()[0] - 1.0).abs() < 1e-4);
+}
+
+/// Parameter counts distinguish total, variable and active parameters.
+#[test]
+fn parameter_counts() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] - 2.0).powi(2) + p[2] + p[3];
+    let result = MnMigrad::new()
+        .add("a", 0.0, 0.5)
+        .add_limited("pinned", 2.0, 0.5, 2.0, 2.0)
+        .add("fixed", 3.0, 0.5)
+        .add_const("c", 4.0)
+        .fix(2)
+        .minimize(&fcn);
+
+    assert_eq!(result.n_total_parameters(), 4);
+    assert_eq!(result.n_variable_params(), 2);
+    assert_eq!(result.n_active_parameters(), 1);
+}
+
+/// Near-flat directions fail the global-minimum candidate gate.
+#[test]
+fn global_minimum_candidate_rejects_flat_direction() {
+    let good = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add("y", -2.0, 1.0)
+        .minimize(&|p: &[f64]| (p[0] - 1.0).powi(2) + 4.0 * (p[1] + 0.5).powi(2));
+    assert!(good.is_global_minimum_candidate());
+
+    // Curvature ratio of 1e7 between the two axes.
+    let flat = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add("y", -2.0, 1.0)
+        .minimize(&|p: &[f64]| (p[0] - 1.0).powi(2) + 1e7 * (p[1] + 0.5).powi(2));
+    assert!(flat.is_valid());
+    assert!(!flat.is_global_minimum_candidate());
+}
+
+/// A gradient tolerance keeps Migrad iterating past a loose EDM criterion.
+#[test]
+fn gradient_tolerance_tightens_convergence() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(4) + (p[1] + 2.0).powi(4);
+    let loose = MnMigrad::new()
+        .add("x", 5.0, 1.0)
+        .add("y", 5.0, 1.0)
+        .tolerance(1e3);
+    let plain = loose.clone().minimize(&fcn);
+    let strict = loose.gradient_tolerance(1e-3).minimize(&fcn);
+
+    let plain_grad = plain.state().gradient().grad().amax();
+    let strict_grad = strict.state().gradient().grad().amax();
+    assert!(strict.is_valid());
+    assert!(strict_grad < 1e-3, "gradient not converged: {strict_grad}");
+    assert!(
+        plain_grad > 1e-3,
+        "EDM-only fit already converged: {plain_grad}"
+    );
+}
+
+/// An early stop ends Migrad at the first state the predicate accepts.
+#[test]
+fn minimize_with_early_stop() {
+    let fcn = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+    let migrad = MnMigrad::new().add("x", -1.2, 0.1).add("y", 1.0, 0.1);
+
+    let full = migrad.minimize(&fcn);
+    let stopped = migrad.minimize_with_early_stop(&fcn, |state| state.fval() < 1.0);
+
+    assert!(full.is_valid());
+    assert!(stopped.reached_call_limit());
+    assert!(!stopped.is_valid());
+    assert!(stopped.fval() < 1.0);
+    assert!(stopped.fval() > full.fval());
+    assert!(stopped.nfcn() < full.nfcn());
+}
+
+/// An absolute EDM threshold is independent of Up and combines by minimum.
+#[test]
+fn tolerance_absolute_sets_edm_threshold() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(4) + (p[1] + 2.0).powi(4);
+    let migrad = MnMigrad::new().add("x", 5.0, 1.0).add("y", 5.0, 1.0);
+
+    let absolute = migrad.clone().tolerance_absolute(1e-7).minimize(&fcn);
+    assert!(absolute.is_valid());
+    assert!(absolute.edm() < 1e-6, "edm {}", absolute.edm());
+
+    // Relative 10 gives an EDM target of 0.02; the absolute one is stricter.
+    let loose = migrad.clone().tolerance(10.0).minimize(&fcn);
+    let both = migrad
+        .tolerance(10.0)
+        .tolerance_absolute(1e-7)
+        .minimize(&fcn);
+    assert!(both.edm() < 1e-6, "edm {}", both.edm());
+    assert!(loose.edm() > both.edm());
+}
+
+/// Straight-line chi-square fit exposing its residuals.
+struct LineFit {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    sigma: Vec<f64>,
+}
+
+impl FCN for LineFit {
+    fn value(&self, par: &[f64]) -> f64 {
+        self.residuals(par).iter().map(|r| r * r).sum()
+    }
+}
+
+impl FCNResiduals for LineFit {
+    fn residuals(&self, par: &[f64]) -> Vec<f64> {
+        self.x
+            .iter()
+            .zip(&self.y)
+            .zip(&self.sigma)
+            .map(|((x, y), s)| (y - (par[0] + par[1] * x)) / s)
+            .collect()
+    }
+}
+
+/// `compute_residuals` evaluates the residuals at the fitted parameters.
+#[test]
+fn compute_residuals_at_minimum() {
+    let fcn = LineFit {
+        x: vec![0.0, 1.0, 2.0, 3.0],
+        y: vec![1.1, 2.9, 5.2, 6.8],
+        sigma: vec![0.1, 0.2, 0.1, 0.2],
+    };
+    let result = MnMigrad::new()
+        .add("a", 0.0, 1.0)
+        .add("b", 0.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let residuals = result.compute_residuals(&fcn);
+    assert_eq!(residuals.len(), 4);
+    let chi2: f64 = residuals.iter().map(|r| r * r).sum();
+    assert!((chi2 - result.fval()).abs() < 1e-12);
+}
+
+/// `params_with_errors` pairs `params()` with the user-state errors.
+#[test]
+fn params_with_errors_in_index_order() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + 4.0 * (p[1] + 2.0).powi(2);
+    let result = MnMigrad::new()
+        .add("a", 0.0, 1.0)
+        .add("b", 0.0, 1.0)
+        .add_const("c", 3.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let pairs = result.params_with_errors();
+    let errors = result.user_state().errors();
+    assert_eq!(pairs.len(), 3);
+    for ((pair, value), error) in pairs.iter().zip(result.params()).zip(errors) {
+        assert_eq!(*pair, (value, error));
+    }
+
+    let named = result.named_params_with_errors();
+    let names: Vec<&str> = named.iter().map(|(n, _, _)| n.as_str()).collect();
+    assert_eq!(names, ["a", "b", "c"]);
+    assert_eq!((named[1].1, named[1].2), pairs[1]);
+}
+
+/// A fit stopped early resumes from its packed user state.
+#[test]
+fn minimize_from_packed_state() {
+    let fcn = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+    let migrad = MnMigrad::new()
+        .add("x", -1.2, 0.1)
+        .add_limited("y", 1.0, 0.1, -5.0, 5.0);
+
+    let partial = migrad.clone().max_fcn(30).minimize(&fcn);
+    assert!(!partial.is_valid());
+
+    let saved = partial.user_state().pack();
+    let state = minuit2::MnUserParameterState::unpack(&saved).unwrap();
+    let resumed = MnMigrad::new().minimize_from_state(&fcn, state).unwrap();
+
+    assert!(resumed.is_valid());
+    assert!(resumed.user_state().parameter(1).has_limits());
+    let p = resumed.params();
+    assert!(
+        (p[0] - 1.0).abs() < 1e-2 && (p[1] - 1.0).abs() < 1e-2,
+        "{p:?}"
+    );
+}
+
+/// Soft bounds follow their parameter by name into a resumed state.
+#[test]
+fn minimize_from_state_remaps_soft_bounds() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] - 3.0).powi(2);
+    let migrad = MnMigrad::new()
+        .add("y", 0.0, 0.5)
+        .add_soft_bounded("x", 1.0, 0.5, 0.0, 2.0, 100.0);
+
+    // The saved layout swaps the parameters: x is now index 0.
+    let mut params = minuit2::MnUserParameters::new();
+    params.add("x", 1.0, 0.5);
+    params.add("y", 0.0, 0.5);
+    let swapped = |p: &[f64]| fcn(&[p[1], p[0]]);
+    let state = minuit2::MnUserParameterState::new(params);
+    let result = migrad.minimize_from_state(&swapped, state).unwrap();
+    assert!(result.is_valid());
+    // Stationary point of (x-3)^2 + 100 (x-2)^2: x = 203 / 101.
+    assert!((result.params()[0] - 203.0 / 101.0).abs() < 1e-3);
+
+    let mut params = minuit2::MnUserParameters::new();
+    params.add("y", 0.0, 0.5);
+    let state = minuit2::MnUserParameterState::new(params);
+    assert_eq!(
+        migrad.minimize_from_state(&fcn, state).err(),
+        Some(ParameterError::UnknownName("x".to_string()))
+    );
+}
+
+/// `reset_values` undoes a `from_minimum` warm start.
+#[test]
+fn reset_values_undoes_warm_start() {
+    let fcn = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+    let migrad = MnMigrad::new().add("x", -1.2, 0.1).add("y", 1.0, 0.1);
+    let fresh = migrad.minimize(&fcn);
+
+    let warm = migrad.clone().from_minimum(&fresh);
+    let warm_fit = warm.minimize(&fcn);
+    assert!(warm_fit.nfcn() < fresh.nfcn());
+    assert!((warm_fit.params()[0] - 1.0).abs() < 1e-3);
+
+    let reset = warm.reset_values().minimize(&fcn);
+    assert_eq!(reset.nfcn(), fresh.nfcn());
+    assert_eq!(reset.params(), fresh.params());
+}
+
+/// `to_starting_params` carries fitted values, errors, limits and fixes.
+#[test]
+fn to_starting_params_seeds_refit() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] - 0.5).powi(2) + p[2];
+    let result = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add_limited("y", 0.0, 0.1, -1.0, 1.0)
+        .add("z", 7.0, 0.5)
+        .fix(2)
+        .minimize(&fcn);
+
+    let start = result.to_starting_params();
+    assert_eq!(start.len(), 3);
+    for (i, (value, error)) in result.params_with_errors().into_iter().enumerate() {
+        assert_eq!(start.params()[i].value(), value);
+        assert_eq!(start.params()[i].error(), error);
+    }
+    assert!(start.params()[1].has_limits());
+    assert!(start.params()[2].is_fixed());
+
+    let state = minuit2::MnUserParameterState::new(start);
+    let refit = MnMigrad::new().minimize_from_state(&fcn, state).unwrap();
+    assert!(refit.is_valid());
+    assert!(refit.nfcn() < result.nfcn());
+}
+
+/// Every gradient stencil finds the Rosenbrock minimum.
+#[test]
+fn gradient_methods_converge() {
+    let fcn = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+    // The default EDM target admits |y - 1| ~ 1e-2 along the flat valley.
+    let migrad = MnMigrad::new()
+        .add("x", -1.2, 0.1)
+        .add("y", 1.0, 0.1)
+        .tolerance(0.01);
+    for method in [
+        GradientMethod::TwoPoint,
+        GradientMethod::FourPoint,
+        GradientMethod::FivePoint,
+    ] {
+        let result = migrad.clone().gradient_method(method).minimize(&fcn);
+        assert!(result.is_valid(), "{method:?}");
+        for p in result.params() {
+            assert!((p - 1.0).abs() < 1e-2, "{method:?}: {p}");
+        }
+    }
+}
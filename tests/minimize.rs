@@ -67,3 +67,91 @@ fn minimize_respects_fixed_and_limited_parameters() {
         params[1]
     );
 }
+
+/// Cloned builders keep parameters, fixed flags, and settings.
+#[test]
+fn cloned_builders_reuse_configuration() {
+    use minuit2::MnSimplex;
+
+    let quad = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] - 2.0).powi(2);
+    let shifted = |p: &[f64]| (p[0] + 1.0).powi(2) + (p[1] - 2.0).powi(2);
+
+    let migrad = MnMigrad::new()
+        .add("x", 0.0, 0.1)
+        .add("y", 5.0, 0.1)
+        .fix(1)
+        .tolerance(0.01);
+    let a = migrad.clone().minimize(&quad);
+    let b = migrad.minimize(&shifted);
+    assert!((a.params()[0] - 1.0).abs() < 1e-3);
+    assert!((b.params()[0] + 1.0).abs() < 1e-3);
+    assert!(
+        (a.params()[1] - 5.0).abs() < 1e-15,
+        "fixed flag is preserved"
+    );
+    assert!((b.params()[1] - 5.0).abs() < 1e-15);
+
+    let simplex = MnSimplex::new().add("x", 0.0, 1.0).add("y", 0.0, 1.0);
+    let s = simplex.clone().minimize(&quad);
+    let t = simplex.minimize(&quad);
+    assert_eq!(s.nfcn(), t.nfcn());
+    assert!((s.params()[1] - 2.0).abs() < 0.25);
+
+    let minimize = MnMinimize::new().add("x", 0.0, 0.1).add("y", 0.0, 0.1);
+    let m = minimize.clone().minimize(&shifted);
+    assert!((m.params()[0] + 1.0).abs() < 1e-3);
+}
+
+/// Multi-start search finds the global minimum of a double well and is
+/// reproducible for a fixed seed.
+#[test]
+fn minimize_n_best_finds_global_minimum() {
+    // Wells near x = -1 (deeper, tilted) and x = +1.
+    let fcn = |p: &[f64]| (p[0] * p[0] - 1.0).powi(2) + 0.3 * p[0] + p[1] * p[1];
+    let builder = MnMinimize::new()
+        .add("x", 1.0, 0.5)
+        .add("y", 0.5, 0.5)
+        .with_seed(7);
+
+    let results = builder.minimize_n_best(&fcn, 12, 4.0);
+    assert_eq!(results.len(), 12);
+    assert!(results.windows(2).all(|w| w[0].fval() <= w[1].fval()));
+    assert!(
+        results[0].params()[0] < 0.0,
+        "best start should reach the left well"
+    );
+
+    let again = builder.minimize_n_best(&fcn, 12, 4.0);
+    let fvals = |r: &[minuit2::FunctionMinimum]| r.iter().map(|m| m.fval()).collect::<Vec<_>>();
+    assert_eq!(fvals(&results), fvals(&again));
+
+    #[cfg(feature = "parallel")]
+    assert_eq!(
+        fvals(&builder.minimize_n_best_parallel(&fcn, 12, 4.0)),
+        fvals(&results)
+    );
+}
+
+/// Simplex-first ordering always runs Simplex before refining with Migrad.
+#[test]
+fn minimize_simplex_first_ordering() {
+    // Double well: local minimum near x = 0.96, global near x = -1.04.
+    let fcn = |p: &[f64]| (p[0] * p[0] - 1.0).powi(2) + 0.3 * p[0];
+
+    let migrad_first = MnMinimize::new().add("x", 2.0, 1.0).minimize(&fcn);
+    let simplex_first = MnMinimize::new()
+        .add("x", 2.0, 1.0)
+        .with_simplex_first(true)
+        .minimize(&fcn);
+    let explicit = MnMinimize::new()
+        .add("x", 2.0, 1.0)
+        .with_migrad_first(false)
+        .minimize(&fcn);
+
+    assert!(migrad_first.is_valid() && simplex_first.is_valid());
+    // Migrad descends into the nearby local well; Simplex steps over it.
+    assert!((migrad_first.params()[0] - 0.96).abs() < 0.01);
+    assert!((simplex_first.params()[0] + 1.04).abs() < 0.01);
+    assert_eq!(simplex_first.params(), explicit.params());
+    assert_eq!(simplex_first.nfcn(), explicit.nfcn());
+}
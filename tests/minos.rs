@@ -150,3 +150,230 @@ fn minos_upper_bound_reports_limit() {
     assert!(!me.upper_new_min());
     assert!(me.nfcn() > 0);
 }
+
+/// Name-based lookup matches index-based MINOS errors and reports unknown names.
+#[test]
+fn minos_errors_by_name() {
+    use minuit2::minos::MinosLookupError;
+
+    let quadratic = |p: &[f64]| p[0] * p[0] + 4.0 * (p[1] - 1.0).powi(2);
+    let result = MnMigrad::new()
+        .add("x", 2.0, 1.0)
+        .add("y", 0.0, 1.0)
+        .minimize(&quadratic);
+    let hesse_result = MnHesse::new().calculate(&quadratic, &result);
+    assert!(hesse_result.is_valid());
+
+    let minos = MnMinos::new(&quadratic, &hesse_result);
+    let me = minos.minos_error(1);
+    assert!(me.is_valid());
+
+    let lower = minos.lower_error_by_name("y").expect("y lower error");
+    let upper = minos.upper_error_by_name("y").expect("y upper error");
+    assert!((lower - me.lower_error()).abs() < 1e-12);
+    assert!((upper - me.upper_error()).abs() < 1e-12);
+    assert!((upper - 0.5).abs() < 0.05, "upper={upper}");
+
+    assert_eq!(
+        minos.lower_error_by_name("z"),
+        Err(MinosLookupError::ParameterNotFound("z".to_string()))
+    );
+}
+
+/// A limit inside the 1σ interval truncates that side of the MINOS interval.
+#[test]
+fn minos_truncated_by_limit() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2);
+    let result = MnMigrad::new()
+        .add_limited("x", 2.0, 0.5, 0.5, 10.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let me = MnMinos::new(&fcn, &result).minos_error(0);
+    assert!(
+        me.lower_truncated(),
+        "lower crossing at x=0 is below the limit"
+    );
+    assert!(!me.upper_truncated());
+    assert!(me.is_truncated_by_limit());
+}
+
+/// A steep sextic wall defeats the parabolic crossing steps; the bisection
+/// fallback still locates F = Fmin + Up.
+#[test]
+fn minos_bisection_fallback_on_steep_wall() {
+    let fcn = |p: &[f64]| p[0] * p[0] + 10.0 * p[0].powi(6) + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 0.3, 0.1)
+        .add("y", 0.1, 0.1)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let err = MnMinos::new(&fcn, &result).minos_error(0);
+    assert!(err.upper_valid() && err.lower_valid());
+    assert!(err.upper().converged_by_bisection());
+    assert!(err.lower().converged_by_bisection());
+
+    let target = result.fval() + result.up();
+    let x0 = result.params()[0];
+    for x in [x0 + err.upper_error(), x0 + err.lower_error()] {
+        let f = fcn(&[x, 0.0]);
+        assert!((f - target).abs() < 0.1 * result.up(), "x={x}, f={f}");
+    }
+}
+
+/// A bare parameter state gives the same MINOS errors as the full minimum.
+#[test]
+fn minos_from_user_state_matches_minimum() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + 0.5 * (p[0] - 1.0).powi(4) + (p[1] * 2.0).powi(2);
+    let result = MnMigrad::new()
+        .add("x", 0.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+    assert!(hesse_result.is_valid());
+
+    let state = hesse_result.user_state().clone();
+    let from_state = MnMinos::from_user_state(&fcn, &state);
+    let from_min = MnMinos::new(&fcn, &hesse_result);
+    for par in 0..2 {
+        let a = from_state.minos_error(par);
+        let b = from_min.minos_error(par);
+        assert!(a.is_valid());
+        assert!((a.lower_error() - b.lower_error()).abs() < 1e-9);
+        assert!((a.upper_error() - b.upper_error()).abs() < 1e-9);
+    }
+}
+
+/// Parallel crossing searches reproduce the serial MINOS errors.
+#[cfg(feature = "parallel")]
+#[test]
+fn minos_error_parallel_matches_serial() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + 0.5 * (p[0] - 1.0).powi(4) + 4.0 * p[1] * p[1];
+
+    let result = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+
+    let serial = MnMinos::new(&fcn, &hesse_result).minos_error(0);
+    let sync = MnMinos::new_sync(&fcn, &hesse_result).with_strategy(1);
+    let parallel = sync.minos_error_parallel(0);
+    // Serial methods stay available through `Deref`.
+    assert_eq!(sync.minos_error(0).upper_error(), serial.upper_error());
+    assert!(parallel.is_valid());
+    assert_eq!(serial.lower_error(), parallel.lower_error());
+    assert_eq!(serial.upper_error(), parallel.upper_error());
+}
+
+/// `with_budget_split` moves the shared call budget between the crossings.
+#[test]
+fn minos_budget_split_between_directions() {
+    let fcn = |p: &[f64]| {
+        (p[0] - 1.0).powi(2)
+            + 3.0 * (p[0] - 1.0).powi(4)
+            + (p[1] - 0.5 * p[0]).powi(4)
+            + p[1] * p[1]
+    };
+
+    let result = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    let hesse_result = MnHesse::new().calculate(&fcn, &result);
+    let reference = MnMinos::new(&fcn, &hesse_result).lower(0);
+    assert!(reference.is_valid());
+
+    // About half the calls the lower crossing needs: too few for either side.
+    let tight = MnMinos::new(&fcn, &hesse_result).with_max_calls(22);
+    assert!(tight.lower(0).at_max_fcn());
+    assert!(tight.upper(0).at_max_fcn());
+
+    let lower_heavy = tight.with_budget_split(0.95);
+    let lower = lower_heavy.lower(0);
+    assert!(lower.is_valid());
+    assert_eq!(lower.value(), reference.value());
+    assert!(lower_heavy.upper(0).at_max_fcn());
+}
+
+/// `minos_with_intermediates` exposes the fixed-parameter Migrad probes.
+#[test]
+fn minos_with_intermediates_traces_probes() {
+    let fcn = |p: &[f64]| p[0].exp() - p[0] + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    let result = MnHesse::new().calculate(&fcn, &result);
+    let minos = MnMinos::new(&fcn, &result);
+
+    let (me, trace) = minos.minos_with_intermediates(0);
+    let plain = minos.minos_error(0);
+    assert!(me.is_valid());
+    assert_eq!(me.lower_error(), plain.lower_error());
+    assert_eq!(me.upper_error(), plain.upper_error());
+    // Each crossing search needs at least two Migrad runs.
+    assert!(trace.len() >= 4, "only {} probes", trace.len());
+
+    let x_min = result.params()[0];
+    for probe in &trace {
+        // x is held fixed in each probe; y is re-minimized.
+        let x = probe.user_state().parameter(0);
+        assert!(x.is_fixed());
+        assert!(probe.params()[1].abs() < 1e-3);
+        assert!(probe.fval() >= result.fval() - 1e-9);
+    }
+    let first_upper = trace
+        .iter()
+        .position(|m| m.params()[0] > x_min)
+        .expect("upper probes");
+    assert!(trace[..first_upper].iter().all(|m| m.params()[0] < x_min));
+    assert!(trace[first_upper..].iter().all(|m| m.params()[0] > x_min));
+}
+
+/// `to_tuple` and `to_dict` export the same numbers as the accessors.
+#[test]
+fn minos_error_export_formats() {
+    let fcn = |p: &[f64]| p[0].exp() - p[0] + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    let result = MnHesse::new().calculate(&fcn, &result);
+    let me = MnMinos::new(&fcn, &result).minos_error(0);
+    assert!(me.is_valid());
+
+    assert_eq!(me.to_tuple(), (me.lower_error(), me.upper_error(), true));
+    let dict = me.to_dict();
+    assert_eq!(dict.len(), 5);
+    assert_eq!(dict["lower"], me.lower_error());
+    assert_eq!(dict["upper"], me.upper_error());
+    assert_eq!(dict["lower_valid"], 1.0);
+    assert_eq!(dict["upper_valid"], 1.0);
+    assert_eq!(dict["value"], me.min());
+}
+
+/// `minimize_with_minos` matches the explicit Migrad → Hesse → MINOS chain.
+#[test]
+fn minimize_with_minos_one_shot() {
+    let fcn = |p: &[f64]| p[0].exp() - p[0] + (p[1] - 0.5 * p[0]).powi(2) + p[2] * p[2];
+    let migrad = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .add("z", 1.0, 1.0);
+
+    let (minimum, errors) = migrad.minimize_with_minos(&fcn, &[0, 2]);
+    assert!(minimum.is_valid());
+    assert!(minimum.has_accurate_covariance());
+    assert_eq!(errors.len(), 2);
+    assert!(!errors.contains_key(&1));
+
+    let reference = MnHesse::new().calculate(&fcn, &migrad.minimize(&fcn));
+    let minos = MnMinos::new(&fcn, &reference);
+    for par in [0, 2] {
+        let expected = minos.minos_error(par);
+        assert!(errors[&par].is_valid());
+        assert_eq!(errors[&par].to_tuple(), expected.to_tuple());
+    }
+}
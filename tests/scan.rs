@@ -1,4 +1,5 @@
-use minuit2::{MnMigrad, MnScan};
+use minuit2::scan::MnParameterScan;
+use minuit2::{MnHesse, MnMigrad, MnScan, MnUserParameters};
 
 /// 1D scan of a quadratic: should produce parabolic profile.
 #[test]
@@ -11,7 +12,7 @@ fn scan_quadratic_profile() {
     assert!(result.is_valid());
 
     let scan = MnScan::new(&|p: &[f64]| p[0] * p[0] + p[1] * p[1], &result);
-    let points = scan.scan(0, 20, -2.0, 2.0);
+    let points = scan.scan(0, 20, -2.0, 2.0).into_points();
 
     assert!(!points.is_empty());
     assert!(points.len() >= 20);
@@ -39,7 +40,7 @@ fn scan_auto_range() {
 
     let scan = MnScan::new(&|p: &[f64]| p[0] * p[0], &result);
     // low == high == 0.0 triggers auto-range
-    let points = scan.scan(0, 10, 0.0, 0.0);
+    let points = scan.scan(0, 10, 0.0, 0.0).into_points();
 
     assert!(!points.is_empty());
     // Check range covers roughly ±2*error
@@ -95,7 +96,7 @@ fn scan_range_and_step_clamping() {
     let scan = MnScan::new(&|p: &[f64]| (p[0] - 1.0).powi(2) + p[1] * p[1], &result);
 
     // nsteps is clamped below range to 2.
-    let points = scan.scan(0, 0, -10.0, 10.0);
+    let points = scan.scan(0, 0, -10.0, 10.0).into_points();
     assert_eq!(points.len(), 3);
 
     let x_min = points.iter().map(|v| v.0).fold(f64::INFINITY, f64::min);
@@ -115,8 +116,8 @@ fn scan_default_matches_serial() {
     assert!(result.is_valid());
 
     let scan = MnScan::new(&|p: &[f64]| p[0] * p[0] + p[1] * p[1], &result);
-    let points_default = scan.scan(0, 25, -2.5, 2.5);
-    let points_serial = scan.scan_serial(0, 25, -2.5, 2.5);
+    let points_default = scan.scan(0, 25, -2.5, 2.5).into_points();
+    let points_serial = scan.scan_serial(0, 25, -2.5, 2.5).into_points();
 
     assert_eq!(points_default.len(), points_serial.len());
     for (a, b) in points_default.iter().zip(points_serial.iter()) {
@@ -141,8 +142,8 @@ fn scan_parallel_matches_serial() {
 
     let fcn = |p: &[f64]| (p[0] - 0.2).powi(2) + 2.0 * (p[1] + 0.4).powi(2) + 0.1 * p[0] * p[1];
     let scan = MnScan::new(&fcn, &result);
-    let serial = scan.scan_serial(0, 40, -2.0, 2.0);
-    let parallel = scan.scan_parallel(0, 40, -2.0, 2.0);
+    let serial = scan.scan_serial(0, 40, -2.0, 2.0).into_points();
+    let parallel = scan.scan_parallel(0, 40, -2.0, 2.0).into_points();
 
     assert_eq!(serial.len(), parallel.len());
     for (a, b) in serial.iter().zip(parallel.iter()) {
@@ -150,3 +151,436 @@ fn scan_parallel_matches_serial() {
         assert!((a.1 - b.1).abs() < 1e-12);
     }
 }
+
+/// A dedicated thread pool gives the same profile as the serial scan.
+#[cfg(feature = "parallel")]
+#[test]
+fn scan_parallel_with_threads_matches_serial() {
+    let fcn = |p: &[f64]| (p[0] - 0.3).powi(2) + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 1.0, 0.5)
+        .add("y", 0.5, 0.5)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result);
+    let serial = scan.scan_serial(0, 30, -1.0, 1.0);
+    let pooled = scan.scan_parallel_with_threads(0, 30, -1.0, 1.0, 2);
+    assert_eq!(serial, pooled);
+}
+
+/// `scan_chi2ndf` divides each profile value by the degrees of freedom.
+#[test]
+fn scan_chi2ndf_divides_by_ndf() {
+    let fcn = |p: &[f64]| 10.0 + (p[0] - 1.0).powi(2);
+    let result = MnMigrad::new().add("x", 0.0, 1.0).minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result);
+    let rows = scan.scan_chi2ndf(0, 10, 0.0, 2.0, 12, 2);
+    let points = scan.scan(0, 10, 0.0, 2.0).into_points();
+    assert_eq!(rows.len(), points.len());
+    for (row, point) in rows.iter().zip(&points) {
+        assert_eq!((row.0, row.1), *point);
+        assert!((row.2 - row.1 / 10.0).abs() < 1e-15);
+    }
+    assert!(scan.scan_chi2ndf(0, 10, 0.0, 2.0, 2, 2).is_empty());
+}
+
+/// `scan_pvalue` converts the scanned chi-square to a p-value.
+#[test]
+fn scan_pvalue_uses_chi2_survival() {
+    // chi2 = 3 + ((x - 1) / 0.1)^2 for 4 data points and 1 parameter.
+    let fcn = |p: &[f64]| 3.0 + ((p[0] - 1.0) / 0.1).powi(2);
+    let result = MnMigrad::new().add("x", 0.0, 0.1).minimize(&fcn);
+    assert!(result.is_valid());
+    let scan = MnScan::new(&fcn, &result);
+
+    let rows = scan.scan_pvalue(0, 10, 0.5, 1.5, 4);
+    assert_eq!(rows.len(), 11);
+    let (x, chi2, p) = rows[5];
+    assert!((x - 1.0).abs() < 1e-12);
+    assert!((chi2 - 3.0).abs() < 1e-9);
+    // Survival function of chi-square with 3 dof at 3.
+    assert!((p - 0.391_625_176_271_089).abs() < 1e-9, "{p}");
+    assert!(rows[..5].windows(2).all(|w| w[0].2 < w[1].2));
+    assert!(rows[5..].windows(2).all(|w| w[0].2 > w[1].2));
+
+    assert!(scan.scan_pvalue(0, 10, 0.5, 1.5, 1).is_empty());
+}
+
+/// `scan_with_model` matches the chi-square parabola on a quadratic FCN.
+#[test]
+fn scan_with_model_overlays_parabola() {
+    let fcn = |p: &[f64]| 4.0 * (p[0] - 1.0).powi(2);
+    let result = MnMigrad::new().add("x", 0.0, 1.0).minimize(&fcn);
+    let result = MnHesse::new().calculate(&fcn, &result);
+    assert!(result.is_valid());
+
+    let x0 = result.params()[0];
+    let sigma = result.user_state().parameter(0).error();
+    let fmin = result.fval();
+    let model = |x: f64| fmin + ((x - x0) / sigma).powi(2);
+    let rows = MnScan::new(&fcn, &result).scan_with_model(0, 10, 0.0, 2.0, &model);
+
+    assert_eq!(rows.len(), 11);
+    for (x, f, m) in rows {
+        assert_eq!(m, model(x));
+        assert!((f - m).abs() < 1e-6, "x={x}: fcn {f} vs model {m}");
+    }
+}
+
+/// `find_minimum` returns the best scan point without the full profile.
+#[test]
+fn scan_find_minimum_returns_best_point() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add("y", 2.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result);
+    let (x_best, f_best) = scan.find_minimum(0, 40, -1.0, 3.0);
+    let points = scan.scan(0, 40, -1.0, 3.0).into_points();
+    let expected = points
+        .iter()
+        .copied()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+
+    assert!((x_best - expected.0).abs() < 1e-12);
+    assert!((f_best - expected.1).abs() < 1e-12);
+    assert!((x_best - 1.0).abs() < 0.11);
+}
+
+/// `find_crossings` brackets the Δχ² = 1 points of a unit parabola.
+#[test]
+fn scan_find_crossings_locates_delta_chi2_one() {
+    let fcn = |p: &[f64]| p[0] * p[0];
+    let result = MnMigrad::new().add("x", 0.5, 1.0).minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result);
+    let target = result.fval() + 1.0;
+    let crossings = scan.find_crossings(0, 100, -3.0, 3.0, target);
+
+    assert_eq!(crossings.len(), 2, "expected two crossings: {crossings:?}");
+    assert!((crossings[0] + 1.0).abs() < 1e-2);
+    assert!((crossings[1] - 1.0).abs() < 1e-2);
+
+    // A target above the whole profile has no crossings.
+    assert!(scan.find_crossings(0, 20, -1.0, 1.0, 100.0).is_empty());
+}
+
+/// `scan_with_fixed` evaluates the conditional profile with overridden values.
+#[test]
+fn scan_with_fixed_overrides_other_parameters() {
+    let fcn = |p: &[f64]| p[0] * p[0] + (p[1] - 1.0).powi(2) + p[0] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 1.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result);
+    let points = scan
+        .scan_with_fixed(0, 10, -1.0, 1.0, &[(1, 3.0)])
+        .into_points();
+
+    assert_eq!(points.len(), 11);
+    for &(x, f) in &points {
+        assert!((f - fcn(&[x, 3.0])).abs() < 1e-12);
+    }
+    // The minimum itself is not modified.
+    assert!((result.user_state().parameter(1).value() - 3.0).abs() > 0.5);
+}
+
+/// `ScanResult` summarizes the best point alongside the raw profile.
+#[test]
+fn scan_result_reports_best_point() {
+    let fcn = |p: &[f64]| (p[0] - 0.5).powi(2);
+    let result = MnMigrad::new().add("x", 2.0, 1.0).minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result).scan(0, 20, -1.5, 2.5);
+    assert_eq!(scan.n_points, 21);
+    assert_eq!(scan.n_points, scan.points.len());
+    assert!((scan.best_x - 0.5).abs() < 1e-12);
+    assert!(scan.best_f.abs() < 1e-12);
+
+    let points = scan.clone().into_points();
+    assert_eq!(points, scan.points);
+}
+
+/// `scan_symmetric` / `scan_n_sigma` center the range on the fitted value.
+#[test]
+fn scan_symmetric_around_fitted_value() {
+    let fcn = |p: &[f64]| (p[0] - 2.0).powi(2) / 0.25;
+    let result = MnMigrad::new().add("x", 0.0, 1.0).minimize(&fcn);
+    assert!(result.is_valid());
+    let value = result.user_state().parameter(0).value();
+    let error = result.user_state().parameter(0).error();
+
+    let scan = MnScan::new(&fcn, &result);
+    let sym = scan.scan_symmetric(0, 10, 0.5);
+    assert_eq!(sym, scan.scan(0, 10, value - 0.5, value + 0.5));
+
+    let two_sigma = scan.scan_n_sigma(0, 10, 2.0);
+    let first = two_sigma.points.first().unwrap().0;
+    let last = two_sigma.points.last().unwrap().0;
+    assert!((first - (value - 2.0 * error)).abs() < 1e-12);
+    assert!((last - (value + 2.0 * error)).abs() < 1e-12);
+    // Two sigma above the minimum is Δχ² = 4.
+    assert!((two_sigma.points[10].1 - result.fval() - 4.0).abs() < 0.05);
+
+    // A non-positive half range is rejected rather than auto-ranged.
+    for half_range in [0.0, -0.5, f64::NAN] {
+        assert_eq!(scan.scan_symmetric(0, 10, half_range).n_points, 0);
+    }
+}
+
+/// `scan_n_sigma_by_name` and `scan_auto` pick the range from the errors.
+#[test]
+fn scan_auto_ranges_by_name_and_three_sigma() {
+    let fcn = |p: &[f64]| (p[0] - 2.0).powi(2) / 0.25 + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 0.0, 1.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result);
+    assert_eq!(
+        scan.scan_n_sigma_by_name("x", 10, 2.0),
+        Some(scan.scan_n_sigma(0, 10, 2.0))
+    );
+    assert_eq!(scan.scan_n_sigma_by_name("z", 10, 2.0), None);
+
+    let mut params = MnUserParameters::new();
+    params.add("x", 2.0, 0.5);
+    params.add("y", 0.0, 1.0);
+    let auto = MnParameterScan::new(&fcn, params, fcn(&[2.0, 0.0])).scan_auto(0, 12);
+    assert!((auto.points.first().unwrap().0 - 0.5).abs() < 1e-12);
+    assert!((auto.points.last().unwrap().0 - 3.5).abs() < 1e-12);
+}
+
+/// `scan_range` evaluates sorted, deduplicated user values.
+#[test]
+fn scan_range_sorts_and_dedups() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 3.0, 1.0)
+        .add("y", 2.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+    let y = result.params()[1];
+
+    let scan = MnScan::new(&fcn, &result).scan_range(0, &[2.0, 0.9, 1.1, 0.9, -1.0, 1.0]);
+    let xs: Vec<f64> = scan.points.iter().map(|p| p.0).collect();
+    assert_eq!(xs, [-1.0, 0.9, 1.0, 1.1, 2.0]);
+    for (x, f) in &scan.points {
+        assert_eq!(*f, fcn(&[*x, y]));
+    }
+    assert_eq!(scan.best_x, 1.0);
+}
+
+/// `scan_range` drops values outside the parameter limits.
+#[test]
+fn scan_range_respects_limits() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add_limited("x", 0.5, 0.1, 0.0, 2.0)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result).scan_range(0, &[-1.0, 0.0, 1.0, 2.0, 2.5]);
+    let xs: Vec<f64> = scan.points.iter().map(|p| p.0).collect();
+    assert_eq!(xs, [0.0, 1.0, 2.0]);
+}
+
+/// `scan_best_of_n` merges scans of disjoint ranges, revealing both wells.
+#[test]
+fn scan_best_of_n_finds_both_wells() {
+    let fcn = |p: &[f64]| (p[0] * p[0] - 1.0).powi(2) + p[1] * p[1];
+    let result = MnMigrad::new()
+        .add("x", 2.0, 0.5)
+        .add("y", 1.0, 1.0)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result);
+    let points = scan.scan_best_of_n(0, 10, &[(0.5, 1.5), (-1.5, -0.5)]);
+    let n_right = scan.scan(0, 10, 0.5, 1.5).n_points;
+    let n_left = scan.scan(0, 10, -1.5, -0.5).n_points;
+    assert_eq!(points.len(), n_right + n_left);
+    assert!(points.windows(2).all(|w| w[0].0 <= w[1].0));
+
+    let y = result.params()[1];
+    for x0 in [-1.0, 1.0] {
+        let &(_, f) = points
+            .iter()
+            .find(|(x, _)| (x - x0).abs() < 1e-12)
+            .expect("grid should hit the minimum");
+        assert_eq!(f, fcn(&[x0, y]));
+    }
+    assert!(points.iter().all(|&(x, _)| x.abs() >= 0.5 - 1e-12));
+}
+
+/// `scan_internal` hands back a scanner that keeps tracking the best point.
+#[test]
+fn scan_internal_shares_best_point() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2);
+    // Both parameters fixed away from the minimum: fval = 8.
+    let result = MnMigrad::new()
+        .add("x", 3.0, 0.5)
+        .add("y", 0.0, 0.5)
+        .fix(0)
+        .fix(1)
+        .minimize(&fcn);
+
+    let mut scanner = MnScan::new(&fcn, &result).scan_internal(0, 10, 0.0, 2.0);
+    assert_eq!(scanner.best_params(), [1.0, 0.0]);
+    assert_eq!(scanner.best_fval(), 4.0);
+
+    scanner.scan(1, 10, -3.0, -1.0);
+    assert_eq!(scanner.best_params(), [1.0, -2.0]);
+    assert_eq!(scanner.best_fval(), 0.0);
+}
+
+/// `scan_and_update` reports whether a scan improved the best point.
+#[test]
+fn scan_and_update_flags_improvement() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2);
+    let mut params = MnUserParameters::new();
+    params.add("x", 3.0, 0.5);
+    params.add("y", 0.0, 0.5);
+    let mut scanner = MnParameterScan::new(&fcn, params, fcn(&[3.0, 0.0]));
+
+    let (points, improved) = scanner.scan_and_update(0, 10, 0.0, 2.0);
+    assert_eq!(points.len(), 11);
+    assert!(improved);
+    assert_eq!(scanner.best_params(), [1.0, 0.0]);
+
+    // Rescanning the same range cannot beat the point already found.
+    let (_, improved) = scanner.scan_and_update(0, 10, 0.0, 2.0);
+    assert!(!improved);
+}
+
+/// `scan_with_step` adds a central-difference derivative of the scan curve.
+#[test]
+fn scan_with_step_derivative_column() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + p[1] * p[1];
+    let mut params = MnUserParameters::new();
+    params.add("x", 1.0, 0.5);
+    params.add("y", 2.0, 1.0);
+
+    let mut scanner = MnParameterScan::new(&fcn, params.clone(), fcn(&[1.0, 2.0]));
+    let rows = scanner.scan_with_step(0, 10, -1.0, 3.0, Some(1e-3), true);
+    assert_eq!(rows.len(), 11);
+    for (x, f, d) in &rows {
+        assert_eq!(*f, fcn(&[*x, 2.0]));
+        // Central differences are exact for a parabola.
+        assert!((d.unwrap() - 2.0 * (x - 1.0)).abs() < 1e-9);
+    }
+
+    let mut scanner = MnParameterScan::new(&fcn, params, fcn(&[1.0, 2.0]));
+    let rows = scanner.scan_with_step(0, 10, -1.0, 3.0, None, false);
+    assert!(rows.iter().all(|(_, _, d)| d.is_none()));
+}
+
+/// `scan_with_step` keeps derivative probes within the limits and rejects
+/// unusable steps.
+#[test]
+fn scan_with_step_respects_limits() {
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2);
+    let mut params = MnUserParameters::new();
+    params.add_limited("x", 1.0, 0.5, 0.0, 2.0);
+
+    let mut scanner = MnParameterScan::new(&fcn, params.clone(), 0.0);
+    let rows = scanner.scan_with_step(0, 4, -1.0, 3.0, Some(0.25), true);
+    assert_eq!(rows.first().unwrap().0, 0.0);
+    assert_eq!(rows.last().unwrap().0, 2.0);
+    // At x = 0 the difference is one-sided over [0, 0.25].
+    let (_, _, d) = rows[0];
+    assert!((d.unwrap() - (fcn(&[0.25]) - fcn(&[0.0])) / 0.25).abs() < 1e-12);
+
+    for step in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let mut scanner = MnParameterScan::new(&fcn, params.clone(), 0.0);
+        assert!(
+            scanner
+                .scan_with_step(0, 4, 0.0, 2.0, Some(step), true)
+                .is_empty()
+        );
+    }
+}
+
+/// `closest_to` and `interpolate` query the profile between grid points.
+#[test]
+fn scan_result_point_queries() {
+    let fcn = |p: &[f64]| 3.0 * p[0] + 1.0;
+    let result = MnMigrad::new()
+        .add_limited("x", 0.5, 0.1, 0.0, 1.0)
+        .minimize(&fcn);
+
+    let scan = MnScan::new(&fcn, &result).scan(0, 10, 0.0, 1.0);
+    let (x, f) = scan.closest_to(0.33).unwrap();
+    assert!((x - 0.3).abs() < 1e-12);
+    assert!((f - fcn(&[x])).abs() < 1e-12);
+
+    // The profile is linear, so interpolation is exact.
+    assert!((scan.interpolate(0.37).unwrap() - fcn(&[0.37])).abs() < 1e-12);
+    assert!((scan.interpolate(1.0).unwrap() - 4.0).abs() < 1e-12);
+    assert_eq!(scan.interpolate(1.5), None);
+    assert_eq!(scan.interpolate(-0.1), None);
+}
+
+/// `scan_log` samples uniformly in log space and reports linear values.
+#[test]
+fn scan_log_spacing() {
+    let fcn = |p: &[f64]| (p[0].ln() - 2.0_f64.ln()).powi(2);
+    let result = MnMigrad::new()
+        .add_lower_limited("w", 1.0, 0.5, 1e-6)
+        .minimize(&fcn);
+    assert!(result.is_valid());
+
+    let scan = MnScan::new(&fcn, &result).scan_log(0, 4, 0.01, 100.0);
+    let xs: Vec<f64> = scan.points.iter().map(|p| p.0).collect();
+    let expected = [0.01, 0.1, 1.0, 10.0, 100.0];
+    for (x, e) in xs.iter().zip(expected) {
+        assert!((x / e - 1.0).abs() < 1e-12, "got {xs:?}");
+    }
+    assert!((scan.best_x - 1.0).abs() < 1e-12);
+
+    // A non-positive low end is clamped to the lower limit; a reversed range
+    // is rejected.
+    let clamped = MnScan::new(&fcn, &result).scan_log(0, 4, -1.0, 10.0);
+    assert_eq!(clamped.points[0].0, 1e-6);
+    assert_eq!(
+        MnScan::new(&fcn, &result)
+            .scan_log(0, 4, 10.0, 1.0)
+            .n_points,
+        0
+    );
+}
+
+/// Chained scans accumulate the best point in `best_params` / `best_fval`.
+#[test]
+fn scan_best_params_after_chained_scans() {
+    use minuit2::scan::MnParameterScan;
+    use minuit2::user_parameters::MnUserParameters;
+
+    let mut params = MnUserParameters::new();
+    params.add("x", 5.0, 1.0);
+    params.add("y", 5.0, 1.0);
+
+    let fcn = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2);
+    let mut scanner = MnParameterScan::new(&fcn, params, fcn(&[5.0, 5.0]));
+    scanner.scan(0, 20, -4.0, 6.0);
+    scanner.scan(1, 20, -7.0, 3.0);
+
+    assert_eq!(scanner.best_params(), vec![1.0, -2.0]);
+    assert_eq!(scanner.best_fval(), scanner.fval());
+    assert!(scanner.best_fval().abs() < 1e-12);
+}
@@ -190,3 +190,35 @@ fn respects_call_limit() {
     assert!(result.reached_call_limit());
     assert!(result.nfcn() >= 4);
 }
+
+/// A relative-improvement threshold ends a flat-bottomed simplex search early.
+#[test]
+fn simplex_min_improvement_stops_early() {
+    use minuit2::mn_fcn::MnFcn;
+    use minuit2::simplex::builder::SimplexBuilder;
+    use minuit2::simplex::seed::SimplexSeedGenerator;
+    use minuit2::{MinuitParameter, MnStrategy, MnUserTransformation};
+
+    let flat = |p: &[f64]| 1.0 + (p[0] - 1.0).powi(4) + (p[1] + 2.0).powi(4);
+    let trafo = MnUserTransformation::new(vec![
+        MinuitParameter::new(0, "x", 4.0, 1.0),
+        MinuitParameter::new(1, "y", 2.0, 1.0),
+    ]);
+    let run = |min_improvement: f64| {
+        let fcn = MnFcn::new(&flat, &trafo);
+        let seed = SimplexSeedGenerator::generate(&fcn, &trafo, &MnStrategy::default());
+        let states = SimplexBuilder::minimum_with_min_improvement(
+            &fcn,
+            &seed,
+            10_000,
+            1e-8,
+            min_improvement,
+        );
+        (states[0].fval(), fcn.num_of_calls())
+    };
+
+    let (f_plain, calls_plain) = run(0.0);
+    let (f_early, calls_early) = run(1e-4);
+    assert!(calls_early < calls_plain, "{calls_early} vs {calls_plain}");
+    assert!(f_early - f_plain < 1e-2, "{f_early} vs {f_plain}");
+}